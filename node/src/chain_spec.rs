@@ -15,41 +15,82 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use minimal_template_runtime::{BalancesConfig, SudoConfig, WASM_BINARY};
+use frame::deps::{
+	codec::Decode,
+	sp_core::{
+		blake2_256,
+		traits::{CallContext, CodeExecutor, RuntimeCode, WrappedRuntimeCode},
+	},
+};
+use minimal_template_runtime::{
+	genesis_config_presets::{DEVELOPMENT, LOCAL_TESTNET, STAGING},
+	WASM_BINARY,
+};
+use sc_executor::WasmExecutor;
 use sc_service::{ChainType, Properties};
-use serde_json::{json, Value};
-use sp_keyring::AccountKeyring;
 
 /// This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec;
 
-fn props() -> Properties {
-	let mut properties = Properties::new();
-	properties.insert("tokenDecimals".to_string(), 0.into());
-	properties.insert("tokenSymbol".to_string(), "MINI".into());
-	properties
+/// Call the `ChainMetadata` runtime API `method` of the runtime `code`.
+///
+/// None of its methods read the state, so they are called on an empty one.
+fn chain_metadata<T: Decode>(
+	executor: &WasmExecutor<sp_io::SubstrateHostFunctions>,
+	code: &[u8],
+	method: &str,
+) -> Result<T, String> {
+	let fetcher = WrappedRuntimeCode(code.into());
+	let runtime_code =
+		RuntimeCode { code_fetcher: &fetcher, heap_pages: None, hash: blake2_256(code).to_vec() };
+	let method = format!("ChainMetadata_{method}");
+
+	let mut state = sp_io::TestExternalities::default();
+	let result = executor
+		.call(&mut state.ext(), &runtime_code, &method, &[], CallContext::Offchain)
+		.0
+		.map_err(|e| format!("Failed to call {method}: {e}"))?;
+	T::decode(&mut &result[..]).map_err(|e| format!("Failed to decode the result of {method}: {e}"))
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
-	Ok(ChainSpec::builder(WASM_BINARY.expect("Development wasm not available"), Default::default())
-		.with_name("Development")
-		.with_id("dev")
-		.with_chain_type(ChainType::Development)
-		.with_genesis_config_patch(testnet_genesis())
-		.with_properties(props())
-		.build())
+/// The chain spec properties, as reported by the runtime `code`.
+fn props(code: &[u8]) -> Result<Properties, String> {
+	let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder().build();
+
+	let mut properties = Properties::new();
+	properties.insert(
+		"tokenDecimals".to_string(),
+		chain_metadata::<u8>(&executor, code, "token_decimals")?.into(),
+	);
+	properties.insert(
+		"tokenSymbol".to_string(),
+		chain_metadata::<String>(&executor, code, "token_symbol")?.into(),
+	);
+	properties.insert(
+		"ss58Format".to_string(),
+		chain_metadata::<u16>(&executor, code, "ss58_prefix")?.into(),
+	);
+	Ok(properties)
 }
 
-/// Configure initial storage state for FRAME pallets.
-fn testnet_genesis() -> Value {
-	use frame::traits::Get;
-	use minimal_template_runtime::interface::{Balance, MinimumBalance};
-	let endowment = <MinimumBalance as Get<Balance>>::get().max(1) * 1000;
-	let balances = AccountKeyring::iter()
-		.map(|a| (a.to_account_id(), endowment))
-		.collect::<Vec<_>>();
-	json!({
-		"balances": BalancesConfig { balances },
-		"sudo": SudoConfig { key: Some(AccountKeyring::Alice.to_account_id()) },
-	})
+/// Build the chain spec of the runtime's genesis preset `preset`.
+///
+/// Only the dev presets carry keys; `staging` is a live chain whose sudo key, council, authorities
+/// and balances must be filled into the spec exported with `build-spec` before it can be started.
+pub fn preset_config(preset: &str) -> Result<ChainSpec, String> {
+	let (name, id, chain_type) = match preset {
+		DEVELOPMENT => ("Development", "dev", ChainType::Development),
+		LOCAL_TESTNET => ("Local Testnet", "local_testnet", ChainType::Local),
+		STAGING => ("Staging", "staging", ChainType::Live),
+		_ => return Err(format!("Unknown genesis preset: {preset}")),
+	};
+
+	let code = WASM_BINARY.ok_or("Runtime wasm not available")?;
+	Ok(ChainSpec::builder(code, Default::default())
+		.with_name(name)
+		.with_id(id)
+		.with_chain_type(chain_type)
+		.with_genesis_config_preset_name(preset)
+		.with_properties(props(code)?)
+		.build())
 }
@@ -15,12 +15,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sc_cli::RunCmd;
+use frame_benchmarking_cli::{BasePath, BlockCmd, MachineCmd, PalletCmd, StorageCmd};
+use sc_cli::{CliConfiguration, DatabaseParams, ImportParams, PruningParams, RunCmd, SharedParams};
 
 #[derive(Debug, Clone)]
 pub enum Consensus {
 	ManualSeal(u64),
+	/// Manual seal without a block timer, blocks are only sealed through the `engine_*` RPCs.
+	ManualSealRpc,
+	/// Manual seal whenever the transaction pool has ready transactions, and after the given
+	/// number of milliseconds without any.
+	OnDemandSeal(u64),
 	InstantSeal,
+	AuraGrandpa,
+	/// Proof-of-work, mining blocks with the given number of CPU threads.
+	Pow(usize),
+	/// BABE slots of the validators' session keys, finalized with GRANDPA.
+	Babe,
 }
 
 impl std::str::FromStr for Consensus {
@@ -29,6 +40,18 @@ impl std::str::FromStr for Consensus {
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		Ok(if s == "instant-seal" {
 			Consensus::InstantSeal
+		} else if s == "aura-grandpa" {
+			Consensus::AuraGrandpa
+		} else if s == "babe" {
+			Consensus::Babe
+		} else if s == "manual-seal-rpc" {
+			Consensus::ManualSealRpc
+		} else if s == "pow" {
+			Consensus::Pow(1)
+		} else if let Some(threads) = s.strip_prefix("pow-") {
+			Consensus::Pow(threads.parse().map_err(|_| "invalid number of mining threads")?)
+		} else if let Some(max_idle) = s.strip_prefix("on-demand-seal-") {
+			Consensus::OnDemandSeal(max_idle.parse().map_err(|_| "invalid max idle time")?)
 		} else if let Some(block_time) = s.strip_prefix("manual-seal-") {
 			Consensus::ManualSeal(block_time.parse().map_err(|_| "invalid block time")?)
 		} else {
@@ -42,13 +65,74 @@ pub struct Cli {
 	#[command(subcommand)]
 	pub subcommand: Option<Subcommand>,
 
+	/// How blocks are authored: `manual-seal-<ms>`, `manual-seal-rpc`, `on-demand-seal-<ms>`,
+	/// `instant-seal`, `aura-grandpa`, `babe` or `pow-<threads>`.
 	#[clap(long, default_value = "manual-seal-3000")]
 	pub consensus: Consensus,
 
+	#[clap(flatten)]
+	pub manual_seal: ManualSealArgs,
+
 	#[clap(flatten)]
 	pub run: RunCmd,
 }
 
+/// Options of the manual and instant seal block authoring, to simulate non-final blocks and reorgs,
+/// and to seal reproducible chains.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ManualSealArgs {
+	/// Finalize blocks this many blocks behind the best block, instead of sealing them final.
+	#[arg(long, default_value_t = 0)]
+	pub finality_delay: u32,
+
+	/// The chance, in percent, that a new block reorgs the best block instead, by sealing two
+	/// blocks on top of its parent. Requires a `--finality-delay` of at least 1.
+	#[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub fork_rate: u8,
+
+	/// Give block `n` the timestamp `genesis_timestamp + n * timestamp_increment`, in milliseconds
+	/// since the unix epoch, instead of the system time, so that identical inputs seal
+	/// byte-identical chains.
+	#[arg(long)]
+	pub genesis_timestamp: Option<u64>,
+
+	/// The milliseconds between the timestamps of consecutive blocks, with `--genesis-timestamp`.
+	#[arg(
+		long,
+		default_value_t = 6000,
+		value_parser = clap::value_parser!(u64).range(1..),
+		requires = "genesis_timestamp"
+	)]
+	pub timestamp_increment: u64,
+}
+
+impl ManualSealArgs {
+	/// Check that the options are consistent, and used by `consensus` rather than silently
+	/// ignored.
+	pub fn check(&self, consensus: &Consensus) -> Result<(), String> {
+		let (seals_timestamps, seals_forks) = match consensus {
+			Consensus::ManualSeal(_) | Consensus::OnDemandSeal(_) => (true, true),
+			// blocks are sealed final or not as asked for by each `engine_createBlock`.
+			Consensus::ManualSealRpc | Consensus::InstantSeal => (true, false),
+			Consensus::AuraGrandpa | Consensus::Pow(_) | Consensus::Babe => (false, false),
+		};
+		if self.genesis_timestamp.is_some() && !seals_timestamps {
+			return Err("`--genesis-timestamp` only applies to the manual and instant seal \
+				consensus"
+				.into());
+		}
+		if (self.finality_delay > 0 || self.fork_rate > 0) && !seals_forks {
+			return Err("`--finality-delay` and `--fork-rate` only apply to the \
+				`manual-seal-<ms>` and `on-demand-seal-<ms>` consensus"
+				.into());
+		}
+		if self.fork_rate > 0 && self.finality_delay == 0 {
+			return Err("`--fork-rate` requires a `--finality-delay` of at least 1".into());
+		}
+		Ok(())
+	}
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
 	/// Key management cli utilities
@@ -78,4 +162,68 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Upgrade the runtime of a running chain to a compiled one.
+	Upgrade(crate::upgrade::UpgradeCmd),
+
+	/// Sub-commands concerned with benchmarking.
+	#[command(subcommand)]
+	Benchmark(BenchmarkCmd),
+}
+
+/// The benchmarks of `frame_benchmarking_cli::BenchmarkCmd` that this node can run. The `overhead`
+/// and `extrinsic` ones are left out, as they need builders of the runtime's extrinsics.
+#[derive(Debug, clap::Subcommand)]
+pub enum BenchmarkCmd {
+	Pallet(PalletCmd),
+	Storage(StorageCmd),
+	Block(BlockCmd),
+	Machine(MachineCmd),
+}
+
+/// Run `$code` on the sub-command of a [`BenchmarkCmd`], bound to `$cmd`.
+macro_rules! unwrap_cmd {
+	($self:expr, $cmd:ident, $code:expr) => {
+		match $self {
+			BenchmarkCmd::Pallet($cmd) => $code,
+			BenchmarkCmd::Storage($cmd) => $code,
+			BenchmarkCmd::Block($cmd) => $code,
+			BenchmarkCmd::Machine($cmd) => $code,
+		}
+	};
+}
+
+/// Forwarded to the sub-command, like `frame_benchmarking_cli::BenchmarkCmd` does.
+impl CliConfiguration for BenchmarkCmd {
+	fn shared_params(&self) -> &SharedParams {
+		unwrap_cmd!(self, cmd, cmd.shared_params())
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		unwrap_cmd!(self, cmd, cmd.import_params())
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		unwrap_cmd!(self, cmd, cmd.database_params())
+	}
+
+	fn base_path(&self) -> sc_cli::Result<Option<BasePath>> {
+		// benchmarks run in a temporary directory unless given one, not in the node's.
+		match unwrap_cmd!(self, cmd, cmd.base_path()) {
+			Ok(None) => Some(BasePath::new_temp_dir()).transpose().map_err(Into::into),
+			other => other,
+		}
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		unwrap_cmd!(self, cmd, cmd.pruning_params())
+	}
+
+	fn trie_cache_maximum_size(&self) -> sc_cli::Result<Option<usize>> {
+		unwrap_cmd!(self, cmd, cmd.trie_cache_maximum_size())
+	}
+
+	fn chain_id(&self, is_dev: bool) -> sc_cli::Result<String> {
+		unwrap_cmd!(self, cmd, cmd.chain_id(is_dev))
+	}
 }
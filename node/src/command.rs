@@ -17,14 +17,17 @@
 
 use crate::{
 	chain_spec,
-	cli::{Cli, Subcommand},
+	cli::{BenchmarkCmd, Cli, Subcommand},
 	service,
 };
+use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
+use minimal_template_runtime::{
+	genesis_config_presets::{DEVELOPMENT, LOCAL_TESTNET, STAGING},
+	interface::OpaqueBlock,
+};
 use sc_cli::SubstrateCli;
 use sc_service::PartialComponents;
-
-#[cfg(feature = "try-runtime")]
-use try_runtime_cli::block_building_info::timestamp_with_aura_info;
+use sp_runtime::traits::HashingFor;
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -53,7 +56,9 @@ impl SubstrateCli for Cli {
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
 		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
+			"dev" | "development" => Box::new(chain_spec::preset_config(DEVELOPMENT)?),
+			"local" | "local_testnet" => Box::new(chain_spec::preset_config(LOCAL_TESTNET)?),
+			"staging" => Box::new(chain_spec::preset_config(STAGING)?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -74,21 +79,23 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				let PartialComponents { client, task_manager, .. } =
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, config.database), task_manager))
 			})
 		},
 		Some(Subcommand::ExportState(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				let PartialComponents { client, task_manager, .. } =
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
@@ -96,7 +103,7 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
@@ -108,7 +115,7 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, backend, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, backend, None), task_manager))
 			})
 		},
@@ -118,16 +125,57 @@ pub fn run() -> sc_cli::Result<()> {
 				cmd.run::<minimal_template_runtime::interface::OpaqueBlock>(&config)
 			})
 		},
+		Some(Subcommand::Upgrade(cmd)) => {
+			sc_cli::LoggerBuilder::new("").init()?;
+			sc_cli::build_runtime()?.block_on(cmd.run())
+		},
+		Some(Subcommand::Benchmark(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| match cmd {
+				BenchmarkCmd::Pallet(cmd) => {
+					if !cfg!(feature = "runtime-benchmarks") {
+						return Err("Runtime benchmarking wasn't enabled when building the node. \
+							You can enable it with `--features runtime-benchmarks`."
+							.into());
+					}
+					cmd.run_with_spec::<HashingFor<OpaqueBlock>, ()>(Some(config.chain_spec))
+				},
+				BenchmarkCmd::Block(cmd) => {
+					let PartialComponents { client, .. } =
+						service::new_partial(&config, &cli.consensus)?;
+					cmd.run(client)
+				},
+				#[cfg(not(feature = "runtime-benchmarks"))]
+				BenchmarkCmd::Storage(_) => Err("Storage benchmarking can be enabled with \
+					`--features runtime-benchmarks`."
+					.into()),
+				#[cfg(feature = "runtime-benchmarks")]
+				BenchmarkCmd::Storage(cmd) => {
+					let PartialComponents { client, backend, .. } =
+						service::new_partial(&config, &cli.consensus)?;
+					let db = backend.expose_db();
+					let storage = backend.expose_storage();
+					cmd.run(config, client, db, storage)
+				},
+				BenchmarkCmd::Machine(cmd) =>
+					cmd.run(&config, SUBSTRATE_REFERENCE_HARDWARE.clone()),
+			})
+		},
 		None => {
+			cli.manual_seal.check(&cli.consensus)?;
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
 				match config.network.network_backend {
 					sc_network::config::NetworkBackendType::Libp2p =>
-						service::new_full::<sc_network::NetworkWorker<_, _>>(config, cli.consensus)
-							.map_err(sc_cli::Error::Service),
+						service::new_full::<sc_network::NetworkWorker<_, _>>(
+							config,
+							cli.consensus,
+							cli.manual_seal,
+						)
+						.map_err(sc_cli::Error::Service),
 					sc_network::config::NetworkBackendType::Litep2p => service::new_full::<
 						sc_network::Litep2pNetworkBackend,
-					>(config, cli.consensus)
+					>(config, cli.consensus, cli.manual_seal)
 					.map_err(sc_cli::Error::Service),
 				}
 			})
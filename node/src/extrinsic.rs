@@ -0,0 +1,72 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed transactions of the runtime, as submitted by the `upgrade` subcommand.
+
+use frame::deps::{
+	codec::Encode,
+	frame_system,
+	sp_core::{crypto::Pair as _, sr25519, Bytes},
+	sp_runtime::{
+		generic::{Era, SignedPayload},
+		traits::Block as BlockT,
+	},
+};
+use minimal_template_runtime::{interface, RuntimeCall};
+use sp_api::RuntimeVersion;
+
+/// `call` signed by `pair` as its transaction `nonce`, for the chain of `genesis_hash` running the
+/// runtime `version`. The transaction is immortal and pays no tip.
+pub fn signed(
+	pair: &sr25519::Pair,
+	call: RuntimeCall,
+	nonce: interface::Nonce,
+	genesis_hash: interface::Hash,
+	version: &RuntimeVersion,
+) -> Bytes {
+	let extra = (
+		frame_system::CheckNonZeroSender::new(),
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(0),
+	);
+	let implicit = (
+		(),
+		version.spec_version,
+		version.transaction_version,
+		genesis_hash,
+		genesis_hash,
+		(),
+		(),
+		(),
+	);
+
+	let payload = SignedPayload::from_raw(call, extra, implicit);
+	let signature = payload.using_encoded(|p| pair.sign(p));
+	let (call, extra, _) = payload.deconstruct();
+	let xt = <interface::Block as BlockT>::Extrinsic::new_signed(
+		call,
+		interface::AccountId::from(pair.public()).into(),
+		signature.into(),
+		extra,
+	);
+	Bytes(xt.encode())
+}
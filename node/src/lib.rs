@@ -17,5 +17,8 @@
 
 pub mod chain_spec;
 pub(crate) mod cli;
+pub mod extrinsic;
+pub mod pow;
 pub mod rpc;
 pub mod service;
+pub(crate) mod upgrade;
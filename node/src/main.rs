@@ -21,8 +21,11 @@
 mod chain_spec;
 mod cli;
 mod command;
+mod extrinsic;
+mod pow;
 mod rpc;
 mod service;
+mod upgrade;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
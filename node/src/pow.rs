@@ -0,0 +1,117 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A Blake2 proof-of-work algorithm, with the difficulty set by the runtime, and a CPU miner.
+
+use frame::deps::{
+	codec::{DecodeAll, Encode},
+	sp_core::{hashing::blake2_256, H256, U256},
+};
+use minimal_template_runtime::interface::OpaqueBlock as Block;
+use sc_consensus_pow::{Error, MiningHandle, PowAlgorithm, Seal};
+use sp_api::ProvideRuntimeApi;
+use sp_consensus_pow::DifficultyApi;
+use sp_runtime::generic::BlockId;
+use std::{sync::Arc, time::Duration};
+
+/// The number of nonces a miner tries before it checks for a new block to mine on.
+const NONCES_PER_ROUND: u64 = 10_000;
+
+/// The work of sealing the block of `pre_hash` with `nonce`.
+fn work(pre_hash: &H256, nonce: u64) -> H256 {
+	H256(blake2_256(&(pre_hash, nonce).encode()))
+}
+
+/// Whether `work`, read as a number, times `difficulty` fits in 256 bits, which is
+/// `difficulty` times less likely than not for a random `work`.
+fn meets_difficulty(work: &H256, difficulty: U256) -> bool {
+	U256::from_big_endian(work.as_bytes()).checked_mul(difficulty).is_some()
+}
+
+/// A proof-of-work algorithm whose seal is a nonce, for which the Blake2 hash of the block's
+/// pre-hash and the nonce meets the difficulty of the runtime's `DifficultyApi`.
+pub struct Blake2Algorithm<C> {
+	client: Arc<C>,
+}
+
+impl<C> Blake2Algorithm<C> {
+	/// Create a new `Blake2Algorithm`, reading the difficulty from `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+// derived `Clone` would require `C: Clone`.
+impl<C> Clone for Blake2Algorithm<C> {
+	fn clone(&self) -> Self {
+		Self { client: self.client.clone() }
+	}
+}
+
+impl<C> PowAlgorithm<Block> for Blake2Algorithm<C>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: DifficultyApi<Block, U256>,
+{
+	type Difficulty = U256;
+
+	fn difficulty(&self, parent: H256) -> Result<U256, Error<Block>> {
+		self.client.runtime_api().difficulty(parent).map_err(|e| {
+			Error::Environment(format!("Fetching the difficulty from the runtime failed: {e}"))
+		})
+	}
+
+	fn verify(
+		&self,
+		_parent: &BlockId<Block>,
+		pre_hash: &H256,
+		_pre_digest: Option<&[u8]>,
+		seal: &Seal,
+		difficulty: U256,
+	) -> Result<bool, Error<Block>> {
+		// a seal is exactly the encoded nonce, so that it cannot carry arbitrary trailing bytes.
+		let Ok(nonce) = u64::decode_all(&mut &seal[..]) else { return Ok(false) };
+		Ok(meets_difficulty(&work(pre_hash, nonce), difficulty))
+	}
+}
+
+/// Mine the blocks proposed by `worker` until the node exits, trying nonces from a random one.
+///
+/// This blocks the calling thread, which should be a dedicated one.
+pub fn mine<C, L, Proof>(worker: MiningHandle<Block, Blake2Algorithm<C>, L, Proof>)
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: DifficultyApi<Block, U256>,
+	L: sc_consensus::JustificationSyncLink<Block>,
+{
+	let mut nonce: u64 = rand::random();
+	loop {
+		let Some(metadata) = worker.metadata() else {
+			// the worker has no block to mine on yet, e.g. while the node is syncing.
+			std::thread::sleep(Duration::from_millis(500));
+			continue;
+		};
+
+		for _ in 0..NONCES_PER_ROUND {
+			nonce = nonce.wrapping_add(1);
+			if meets_difficulty(&work(&metadata.pre_hash, nonce), metadata.difficulty) {
+				futures::executor::block_on(worker.submit(nonce.encode()));
+				break;
+			}
+		}
+	}
+}
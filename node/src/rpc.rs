@@ -22,8 +22,13 @@
 
 #![warn(missing_docs)]
 
+pub mod chain_metadata;
+pub mod dry_run;
+
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
-use minimal_template_runtime::interface::{AccountId, Nonce, OpaqueBlock};
+use minimal_template_runtime::interface::{AccountId, Balance, Hash, Nonce, OpaqueBlock};
+use sc_consensus_manual_seal::EngineCommand;
 use sc_transaction_pool_api::TransactionPool;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use std::sync::Arc;
@@ -38,6 +43,8 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// The command sink of the manual seal engine, if the node is sealing blocks manually.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
 }
 
 #[docify::export]
@@ -55,13 +62,28 @@ where
 		+ 'static,
 	C::Api: sp_block_builder::BlockBuilder<OpaqueBlock>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
+	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<OpaqueBlock, Balance>,
+	C::Api: minimal_template_runtime::ChainMetadata<OpaqueBlock>,
+	C::Api: minimal_template_runtime::DryRunApi<OpaqueBlock>,
 	P: TransactionPool + 'static,
 {
+	use chain_metadata::{ChainMetadataApiServer, ChainMetadataRpc};
+	use dry_run::{DryRun, DryRunApiServer};
+	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, command_sink } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(ChainMetadataRpc::new(client.clone()).into_rpc())?;
+	module.merge(DryRun::new(client, deny_unsafe).into_rpc())?;
+
+	if let Some(command_sink) = command_sink {
+		// `engine_createBlock` and `engine_finalizeBlock`, to seal and finalize blocks on demand.
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
 
 	Ok(module)
 }
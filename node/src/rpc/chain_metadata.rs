@@ -0,0 +1,95 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC methods exposing the runtime's `ChainMetadata` API.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use minimal_template_runtime::ChainMetadata;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use std::{marker::PhantomData, sync::Arc};
+
+/// The native token of the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+	/// The name of the token.
+	pub name: String,
+	/// The symbol of the token.
+	pub symbol: String,
+	/// The number of decimals of the token.
+	pub decimals: u8,
+	/// The minimum balance of an account.
+	pub existential_deposit: NumberOrHex,
+	/// The SS58 prefix of the chain's addresses.
+	pub ss58_prefix: u16,
+}
+
+/// ChainMetadata RPC methods.
+#[rpc(server)]
+pub trait ChainMetadataApi<BlockHash> {
+	/// Returns the native token of the chain, as of block `at`, or the best block if omitted.
+	#[method(name = "chainMetadata_tokenInfo")]
+	fn token_info(&self, at: Option<BlockHash>) -> RpcResult<TokenInfo>;
+}
+
+/// Error code of a failed call to the runtime.
+const RUNTIME_ERROR: i32 = 1;
+
+/// An implementation of the ChainMetadata RPC methods on a full client.
+pub struct ChainMetadataRpc<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> ChainMetadataRpc<C, B> {
+	/// Create a new `ChainMetadataRpc` on `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> ChainMetadataApiServer<<Block as BlockT>::Hash> for ChainMetadataRpc<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ChainMetadata<Block>,
+{
+	fn token_info(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<TokenInfo> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let runtime_error = |e: sp_api::ApiError| {
+			ErrorObject::owned(
+				RUNTIME_ERROR,
+				"Unable to query the token info.",
+				Some(e.to_string()),
+			)
+		};
+
+		Ok(TokenInfo {
+			name: api.token_name(at).map_err(runtime_error)?.into(),
+			symbol: api.token_symbol(at).map_err(runtime_error)?.into(),
+			decimals: api.token_decimals(at).map_err(runtime_error)?,
+			existential_deposit: api.existential_deposit(at).map_err(runtime_error)?.into(),
+			ss58_prefix: api.ss58_prefix(at).map_err(runtime_error)?,
+		})
+	}
+}
@@ -0,0 +1,112 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC methods exposing the runtime's `DryRunApi`.
+
+use frame::deps::{
+	codec::{Decode, Encode},
+	sp_core::Bytes,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use minimal_template_runtime::{DryRunApi as DryRunRuntimeApi, OriginCaller, RuntimeCall};
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{marker::PhantomData, sync::Arc};
+
+/// DryRun RPC methods.
+#[rpc(server)]
+pub trait DryRunApi<BlockHash> {
+	/// Dispatches the SCALE encoded `call` from the SCALE encoded `origin` on top of block `at`,
+	/// or the best block if omitted, without committing its effects.
+	///
+	/// Returns the SCALE encoded `CallDryRunEffects`: the dispatch result, the emitted events and
+	/// the weight used.
+	#[method(name = "dryRun_call")]
+	fn dry_run_call(&self, origin: Bytes, call: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The origin or the call was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+/// An implementation of the DryRun RPC methods on a full client.
+pub struct DryRun<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> DryRun<C, B> {
+	/// Create a new `DryRun` on `client`.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> DryRunApiServer<<Block as BlockT>::Hash> for DryRun<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DryRunRuntimeApi<Block>,
+{
+	fn dry_run_call(
+		&self,
+		origin: Bytes,
+		call: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		// any origin can be used, and a call can be as heavy as a whole block.
+		self.deny_unsafe.check_if_safe()?;
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let decode_error = |e: frame::deps::codec::Error| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to dry run the call.",
+				Some(e.to_string()),
+			)
+		};
+		let origin = OriginCaller::decode(&mut &*origin).map_err(decode_error)?;
+		let call = RuntimeCall::decode(&mut &*call).map_err(decode_error)?;
+
+		let effects = api.dry_run_call(at, origin, call).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to dry run the call.",
+				Some(e.to_string()),
+			)
+		})?;
+		Ok(effects.encode().into())
+	}
+}
@@ -15,17 +15,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use futures::FutureExt;
-use minimal_template_runtime::{interface::OpaqueBlock as Block, RuntimeApi};
-use sc_client_api::backend::Backend;
+use futures::{
+	channel::mpsc,
+	future::{self, Either},
+	FutureExt, SinkExt, StreamExt,
+};
+use minimal_template_runtime::{
+	interface::{Hash, OpaqueBlock as Block},
+	RuntimeApi,
+};
+use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use rand::Rng;
+use sc_client_api::{backend::Backend, BlockBackend};
+use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_babe::BabeWorkerHandle;
+use sc_consensus_manual_seal::EngineCommand;
 use sc_executor::WasmExecutor;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sc_transaction_pool_api::OffchainTransactionPoolFactory;
-use sp_runtime::traits::Block as BlockT;
-use std::sync::Arc;
-
-use crate::cli::Consensus;
+use sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	cli::{Consensus, ManualSealArgs},
+	pow,
+};
 
 #[cfg(feature = "runtime-benchmarks")]
 type HostFunctions =
@@ -41,6 +61,14 @@ pub(crate) type FullClient =
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
+type GrandpaBlockImport =
+	sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
+type GrandpaLinkHalf = sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>;
+type BabeBlockImport = sc_consensus_babe::BabeBlockImport<Block, FullClient, GrandpaBlockImport>;
+
+/// The number of blocks between GRANDPA justifications that are always imported and stored.
+const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
+
 /// Assembly of PartialComponents (enough to run chain ops subcommands)
 pub type Service = sc_service::PartialComponents<
 	FullClient,
@@ -48,10 +76,15 @@ pub type Service = sc_service::PartialComponents<
 	FullSelectChain,
 	sc_consensus::DefaultImportQueue<Block>,
 	sc_transaction_pool::FullPool<Block, FullClient>,
-	Option<Telemetry>,
+	(
+		Option<Telemetry>,
+		Option<(GrandpaBlockImport, GrandpaLinkHalf)>,
+		Option<sc_consensus::BoxBlockImport<Block>>,
+		Option<(BabeBlockImport, sc_consensus_babe::BabeLink<Block>, BabeWorkerHandle<Block>)>,
+	),
 >;
 
-pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
+pub fn new_partial(config: &Configuration, consensus: &Consensus) -> Result<Service, ServiceError> {
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -88,11 +121,123 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		client.clone(),
 	);
 
-	let import_queue = sc_consensus_manual_seal::import_queue(
-		Box::new(client.clone()),
-		&task_manager.spawn_essential_handle(),
-		config.prometheus_registry(),
-	);
+	// both aura and babe blocks are finalized by GRANDPA.
+	let grandpa_block_import = || {
+		sc_consensus_grandpa::block_import(
+			client.clone(),
+			GRANDPA_JUSTIFICATION_PERIOD,
+			&client,
+			select_chain.clone(),
+			telemetry.as_ref().map(|x| x.handle()),
+		)
+	};
+
+	let (import_queue, grandpa, pow_block_import, babe) = match consensus {
+		Consensus::AuraGrandpa => {
+			let (grandpa_block_import, grandpa_link) = grandpa_block_import()?;
+
+			let cidp_client = client.clone();
+			let import_queue =
+				sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(ImportQueueParams {
+					block_import: grandpa_block_import.clone(),
+					justification_import: Some(Box::new(grandpa_block_import.clone())),
+					client: client.clone(),
+					create_inherent_data_providers: move |parent_hash, ()| {
+						let cidp_client = cidp_client.clone();
+						async move {
+							let slot_duration = sc_consensus_aura::standalone::slot_duration_at(
+								&*cidp_client,
+								parent_hash,
+							)?;
+							let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+							let slot = sc_consensus_aura::InherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+							Ok((slot, timestamp))
+						}
+					},
+					spawner: &task_manager.spawn_essential_handle(),
+					registry: config.prometheus_registry(),
+					check_for_equivocation: Default::default(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+					compatibility_mode: Default::default(),
+				})?;
+
+			(import_queue, Some((grandpa_block_import, grandpa_link)), None, None)
+		},
+		Consensus::Babe => {
+			let (grandpa_block_import, grandpa_link) = grandpa_block_import()?;
+			let (block_import, babe_link) = sc_consensus_babe::block_import(
+				sc_consensus_babe::configuration(&*client)?,
+				grandpa_block_import.clone(),
+				client.clone(),
+			)?;
+
+			let slot_duration = babe_link.config().slot_duration();
+			let (import_queue, worker_handle) = sc_consensus_babe::import_queue(
+				sc_consensus_babe::ImportQueueParams {
+					link: babe_link.clone(),
+					block_import: block_import.clone(),
+					justification_import: Some(Box::new(grandpa_block_import.clone())),
+					client: client.clone(),
+					select_chain: select_chain.clone(),
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+						let slot = sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+							*timestamp,
+							slot_duration,
+						);
+						Ok((slot, timestamp))
+					},
+					spawner: &task_manager.spawn_essential_handle(),
+					registry: config.prometheus_registry(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+					offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
+						transaction_pool.clone(),
+					),
+				},
+			)?;
+
+			(
+				import_queue,
+				Some((grandpa_block_import, grandpa_link)),
+				None,
+				Some((block_import, babe_link, worker_handle)),
+			)
+		},
+		Consensus::Pow(_) => {
+			let pow_block_import = sc_consensus_pow::PowBlockImport::new(
+				client.clone(),
+				client.clone(),
+				pow::Blake2Algorithm::new(client.clone()),
+				0,
+				select_chain.clone(),
+				|_, ()| async { Ok(sp_timestamp::InherentDataProvider::from_system_time()) },
+			);
+
+			let import_queue = sc_consensus_pow::import_queue(
+				Box::new(pow_block_import.clone()),
+				None,
+				pow::Blake2Algorithm::new(client.clone()),
+				&task_manager.spawn_essential_handle(),
+				config.prometheus_registry(),
+			)?;
+
+			(import_queue, None, Some(Box::new(pow_block_import) as _), None)
+		},
+		Consensus::ManualSeal(_) |
+		Consensus::ManualSealRpc |
+		Consensus::OnDemandSeal(_) |
+		Consensus::InstantSeal => {
+			let import_queue = sc_consensus_manual_seal::import_queue(
+				Box::new(client.clone()),
+				&task_manager.spawn_essential_handle(),
+				config.prometheus_registry(),
+			);
+			(import_queue, None, None, None)
+		},
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -102,14 +247,209 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (telemetry),
+		other: (telemetry, grandpa, pow_block_import, babe),
+	})
+}
+
+/// The timestamp inherent of a block sealed on top of `parent_hash`: the system time, or with a
+/// `--genesis-timestamp` one that only depends on the block number, failing once that overflows.
+fn seal_timestamp(
+	client: &FullClient,
+	parent_hash: Hash,
+	args: &ManualSealArgs,
+) -> Result<sp_timestamp::InherentDataProvider, sp_blockchain::Error> {
+	let Some(genesis_timestamp) = args.genesis_timestamp else {
+		return Ok(sp_timestamp::InherentDataProvider::from_system_time());
+	};
+	let parent_number = client
+		.number(parent_hash)?
+		.ok_or_else(|| sp_blockchain::Error::UnknownBlock(parent_hash.to_string()))?;
+	let timestamp = u64::from(parent_number)
+		.checked_add(1)
+		.and_then(|number| number.checked_mul(args.timestamp_increment))
+		.and_then(|offset| offset.checked_add(genesis_timestamp))
+		.ok_or_else(|| {
+			sp_blockchain::Error::Application(
+				format!("the timestamp of the block after #{parent_number} overflows").into(),
+			)
+		})?;
+	Ok(sp_timestamp::InherentDataProvider::new(timestamp.into()))
+}
+
+/// Metrics of the manual seal block timer.
+struct BlockTimerMetrics {
+	skipped_ticks: Counter<U64>,
+}
+
+impl BlockTimerMetrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			skipped_ticks: register(
+				Counter::new(
+					"substrate_manual_seal_skipped_ticks_total",
+					"Number of block timer ticks skipped while the manual seal engine was busy",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Seal a block on top of `parent_hash`, or the best block if `None`, returning its hash.
+///
+/// Waits for room in the command channel and for the block to be sealed. Fails only if the
+/// manual seal engine is gone.
+async fn seal_block(
+	sink: &mut mpsc::Sender<EngineCommand<Hash>>,
+	parent_hash: Option<Hash>,
+	finalize: bool,
+) -> Result<Option<Hash>, mpsc::SendError> {
+	let (sender, created) = futures::channel::oneshot::channel();
+	sink.send(EngineCommand::SealNewBlock {
+		create_empty: true,
+		finalize,
+		parent_hash,
+		sender: Some(sender),
+	})
+	.await?;
+
+	Ok(match created.await {
+		Ok(Ok(created)) => Some(created.hash),
+		Ok(Err(e)) => {
+			log::warn!("Failed to seal a block on top of {parent_hash:?}: {e}");
+			None
+		},
+		Err(_) => None,
 	})
 }
 
+/// Seal a new block, reorging the best block with a chance of
+/// `args.fork_rate` percent, and finalize `args.finality_delay` blocks behind the best block.
+async fn seal_tick(
+	client: &FullClient,
+	sink: &mut mpsc::Sender<EngineCommand<Hash>>,
+	args: &ManualSealArgs,
+) -> Result<(), mpsc::SendError> {
+	let finalize = args.finality_delay == 0;
+	let info = client.info();
+	// only a non-final best block can be reorged, by building a longer chain off its parent.
+	let fork_point = rand::thread_rng()
+		.gen_ratio(args.fork_rate.into(), 100)
+		.then(|| client.header(info.best_hash).ok().flatten())
+		.flatten()
+		.filter(|best| *best.number() > info.finalized_number)
+		.map(|best| *best.parent_hash());
+
+	match fork_point {
+		Some(parent_hash) => {
+			log::info!("🍴 Reorging best block #{} ({})", info.best_number, info.best_hash);
+			if let Some(sibling) = seal_block(sink, Some(parent_hash), finalize).await? {
+				seal_block(sink, Some(sibling), finalize).await?;
+			}
+		},
+		None => {
+			seal_block(sink, None, finalize).await?;
+		},
+	}
+
+	if finalize {
+		return Ok(());
+	}
+	let info = client.info();
+	let target = info.best_number.saturating_sub(args.finality_delay);
+	if target > info.finalized_number {
+		if let Ok(Some(hash)) = client.hash(target) {
+			sink.send(EngineCommand::FinalizeBlock { hash, sender: None, justification: None })
+				.await?;
+		}
+	}
+	Ok(())
+}
+
+/// Drive the manual seal engine with a block every `block_time` milliseconds.
+///
+/// A tick waits for its block to be sealed, and the ticks that pass meanwhile are skipped rather
+/// than queued up behind it, so a stalled engine slows block production down instead of crashing
+/// the node.
+async fn run_block_timer(
+	client: Arc<FullClient>,
+	mut sink: mpsc::Sender<EngineCommand<Hash>>,
+	block_time: u64,
+	args: ManualSealArgs,
+	metrics: Option<BlockTimerMetrics>,
+) {
+	let block_time = Duration::from_millis(block_time.max(1));
+	let mut next_tick = Instant::now() + block_time;
+	loop {
+		futures_timer::Delay::new(next_tick.saturating_duration_since(Instant::now())).await;
+
+		if let Err(e) = seal_tick(&client, &mut sink, &args).await {
+			log::error!("Stopping the block timer, the manual seal engine is gone: {e}");
+			return;
+		}
+
+		next_tick += block_time;
+		let now = Instant::now();
+		if next_tick <= now {
+			let skipped = ((now - next_tick).as_millis() / block_time.as_millis()) as u32 + 1;
+			log::warn!("⏳ Block production is lagging behind, skipping {skipped} block ticks");
+			next_tick += block_time * skipped;
+			if let Some(metrics) = &metrics {
+				metrics.skipped_ticks.inc_by(skipped.into());
+			}
+		}
+	}
+}
+
+/// Drive the manual seal engine with a block whenever the transaction pool has ready transactions,
+/// or once no block was sealed for `max_idle` milliseconds, instead of on every tick of a timer.
+///
+/// Transactions that do not fit in one block are sealed into the next ones right away, without
+/// waiting for another import. Once a block seals none of the ready transactions, e.g. as they
+/// fail when applied, sealing waits for the next import or `max_idle` again.
+async fn run_on_demand_sealing(
+	client: Arc<FullClient>,
+	pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+	mut sink: mpsc::Sender<EngineCommand<Hash>>,
+	max_idle: u64,
+	args: ManualSealArgs,
+) {
+	let max_idle = Duration::from_millis(max_idle);
+	let mut imports = pool.import_notification_stream();
+	let mut idle_until = Instant::now() + max_idle;
+	loop {
+		let idle = futures_timer::Delay::new(idle_until.saturating_duration_since(Instant::now()));
+		match future::select(imports.next(), idle).await {
+			// the pool is gone, and the node with it.
+			Either::Left((None, _)) => return,
+			// the imported transactions may have been sealed into an earlier block already.
+			Either::Left((Some(_), _)) if pool.status().ready == 0 => continue,
+			Either::Left(_) | Either::Right(_) => {},
+		}
+
+		let mut ready = pool.status().ready;
+		loop {
+			if let Err(e) = seal_tick(&client, &mut sink, &args).await {
+				log::error!("Stopping on demand sealing, the manual seal engine is gone: {e}");
+				return;
+			}
+			// wait for the pool to drop what was just sealed before checking what is left.
+			let best = client.info().best_number;
+			let left = pool.ready_at(best).await.count();
+			if left == 0 || left >= ready {
+				break;
+			}
+			ready = left;
+		}
+		idle_until = Instant::now() + max_idle;
+	}
+}
+
 /// Builds a new service for a full client.
 pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	consensus: Consensus,
+	manual_seal: ManualSealArgs,
 ) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
@@ -119,10 +459,10 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: mut telemetry,
-	} = new_partial(&config)?;
+		other: (mut telemetry, grandpa, pow_block_import, babe),
+	} = new_partial(&config, &consensus)?;
 
-	let net_config = sc_network::config::FullNetworkConfiguration::<
+	let mut net_config = sc_network::config::FullNetworkConfiguration::<
 		Block,
 		<Block as BlockT>::Hash,
 		Network,
@@ -131,6 +471,29 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
 	);
 
+	// GRANDPA gossips votes on its own protocol, and serves warp sync proofs from its authority
+	// set changes.
+	let grandpa = grandpa.map(|(block_import, link)| {
+		let protocol_name = sc_consensus_grandpa::protocol_standard_name(
+			&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
+			&config.chain_spec,
+		);
+		let (protocol_config, notification_service) =
+			sc_consensus_grandpa::grandpa_peers_set_config::<_, Network>(
+				protocol_name.clone(),
+				metrics.clone(),
+				net_config.peer_store_handle(),
+			);
+		net_config.add_notification_protocol(protocol_config);
+
+		let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
+			backend.clone(),
+			link.shared_authority_set().clone(),
+			Vec::default(),
+		));
+		(block_import, link, protocol_name, notification_service, warp_sync)
+	});
+
 	let (network, system_rpc_tx, tx_handler_controller, network_starter, sync_service) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
 			config: &config,
@@ -140,7 +503,9 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 			import_queue,
 			net_config,
 			block_announce_validator_builder: None,
-			warp_sync_params: None,
+			warp_sync_params: grandpa
+				.as_ref()
+				.map(|(.., warp_sync)| sc_service::WarpSyncParams::WithProvider(warp_sync.clone())),
 			block_relay: None,
 			metrics,
 		})?;
@@ -166,21 +531,39 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		);
 	}
 
+	// the manual seal engine is driven by the commands of its block timer and `engine_*` RPCs.
+	let (command_sink, commands_stream) = match consensus {
+		Consensus::ManualSeal(_) | Consensus::ManualSealRpc | Consensus::OnDemandSeal(_) => {
+			let (sink, stream) = mpsc::channel(1024);
+			(Some(sink), Some(stream))
+		},
+		Consensus::InstantSeal | Consensus::AuraGrandpa | Consensus::Pow(_) | Consensus::Babe =>
+			(None, None),
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let command_sink = command_sink.clone();
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
 
 	let prometheus_registry = config.prometheus_registry().cloned();
+	let role = config.role.clone();
+	let force_authoring = config.force_authoring;
+	let name = config.network.node_name.clone();
 
 	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
-		network,
+		network: network.clone(),
 		client: client.clone(),
 		keystore: keystore_container.keystore(),
 		task_manager: &mut task_manager,
@@ -189,7 +572,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		backend,
 		system_rpc_tx,
 		tx_handler_controller,
-		sync_service,
+		sync_service: sync_service.clone(),
 		config,
 		telemetry: telemetry.as_mut(),
 	})?;
@@ -202,8 +585,48 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		telemetry.as_ref().map(|x| x.handle()),
 	);
 
+	// GRANDPA finalizes the blocks of the aura-grandpa and babe consensus. Only authorities vote,
+	// the other nodes just follow the finality of their votes.
+	let grandpa_block_import = match grandpa {
+		Some((block_import, link, protocol_name, notification_service, _)) => {
+			let keystore = role.is_authority().then(|| keystore_container.keystore());
+			let grandpa_params = sc_consensus_grandpa::GrandpaParams {
+				config: sc_consensus_grandpa::Config {
+					gossip_duration: std::time::Duration::from_millis(333),
+					justification_generation_period: GRANDPA_JUSTIFICATION_PERIOD,
+					name: Some(name),
+					observer_enabled: false,
+					keystore,
+					local_role: role.clone(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+					protocol_name,
+				},
+				link,
+				network,
+				sync: Arc::new(sync_service.clone()),
+				notification_service,
+				voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
+				prometheus_registry: prometheus_registry.clone(),
+				shared_voter_state: sc_consensus_grandpa::SharedVoterState::empty(),
+				telemetry: telemetry.as_ref().map(|x| x.handle()),
+				offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
+					transaction_pool.clone(),
+				),
+			};
+
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"grandpa-voter",
+				None,
+				sc_consensus_grandpa::run_grandpa_voter(grandpa_params)?,
+			);
+			Some(block_import)
+		},
+		None => None,
+	};
+
 	match consensus {
 		Consensus::InstantSeal => {
+			let cidp_client = client.clone();
 			let params = sc_consensus_manual_seal::InstantSealParams {
 				block_import: client.clone(),
 				env: proposer,
@@ -211,8 +634,9 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				pool: transaction_pool,
 				select_chain,
 				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
+				create_inherent_data_providers: move |parent_hash, ()| {
+					let timestamp = seal_timestamp(&cidp_client, parent_hash, &manual_seal);
+					async move { Ok(timestamp?) }
 				},
 			};
 
@@ -224,20 +648,38 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
-		Consensus::ManualSeal(block_time) => {
-			let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
-			task_manager.spawn_handle().spawn("block_authoring", None, async move {
-				loop {
-					futures_timer::Delay::new(std::time::Duration::from_millis(block_time)).await;
-					sink.try_send(sc_consensus_manual_seal::EngineCommand::SealNewBlock {
-						create_empty: true,
-						finalize: true,
-						parent_hash: None,
-						sender: None,
-					})
-					.unwrap();
-				}
-			});
+		Consensus::ManualSeal(_) | Consensus::ManualSealRpc | Consensus::OnDemandSeal(_) => {
+			let commands_stream =
+				commands_stream.expect("the command channel is built for manual seal; qed");
+			let cidp_client = client.clone();
+			let cidp_args = manual_seal.clone();
+
+			if let Consensus::ManualSeal(block_time) = consensus {
+				let sink = command_sink.expect("the command channel is built for manual seal; qed");
+				let metrics = prometheus_registry
+					.as_ref()
+					.map(BlockTimerMetrics::register)
+					.transpose()
+					.map_err(|e| ServiceError::Other(format!("Failed to register metrics: {e}")))?;
+				task_manager.spawn_handle().spawn(
+					"block_authoring",
+					None,
+					run_block_timer(client.clone(), sink, block_time, manual_seal, metrics),
+				);
+			} else if let Consensus::OnDemandSeal(max_idle) = consensus {
+				let sink = command_sink.expect("the command channel is built for manual seal; qed");
+				task_manager.spawn_handle().spawn(
+					"block_authoring",
+					None,
+					run_on_demand_sealing(
+						client.clone(),
+						transaction_pool.clone(),
+						sink,
+						max_idle,
+						manual_seal,
+					),
+				);
+			}
 
 			let params = sc_consensus_manual_seal::ManualSealParams {
 				block_import: client.clone(),
@@ -247,8 +689,9 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				select_chain,
 				commands_stream: Box::pin(commands_stream),
 				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
+				create_inherent_data_providers: move |parent_hash, ()| {
+					let timestamp = seal_timestamp(&cidp_client, parent_hash, &cidp_args);
+					async move { Ok(timestamp?) }
 				},
 			};
 			let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
@@ -259,6 +702,119 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
+		Consensus::AuraGrandpa => {
+			let block_import = grandpa_block_import
+				.expect("the GRANDPA parts are built for the aura-grandpa consensus; qed");
+
+			if role.is_authority() {
+				let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+				let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
+					StartAuraParams {
+						slot_duration,
+						client,
+						select_chain,
+						block_import,
+						proposer_factory: proposer,
+						create_inherent_data_providers: move |_, ()| async move {
+							let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+							let slot = sc_consensus_aura::InherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+							Ok((slot, timestamp))
+						},
+						force_authoring,
+						backoff_authoring_blocks: Option::<()>::None,
+						keystore: keystore_container.keystore(),
+						sync_oracle: sync_service.clone(),
+						justification_sync_link: sync_service.clone(),
+						block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+						max_block_proposal_slot_portion: None,
+						telemetry: telemetry.as_ref().map(|x| x.handle()),
+						compatibility_mode: Default::default(),
+					},
+				)?;
+
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"aura",
+					Some("block-authoring"),
+					aura,
+				);
+			}
+		},
+		Consensus::Pow(threads) => {
+			let block_import =
+				pow_block_import.expect("the pow block import is built for the pow consensus; qed");
+
+			let (worker, worker_task) = sc_consensus_pow::start_mining_worker(
+				block_import,
+				client.clone(),
+				select_chain,
+				pow::Blake2Algorithm::new(client.clone()),
+				proposer,
+				sync_service.clone(),
+				sync_service,
+				None,
+				|_, ()| async { Ok(sp_timestamp::InherentDataProvider::from_system_time()) },
+				Duration::from_secs(10),
+				Duration::from_secs(10),
+			);
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"pow",
+				Some("block-authoring"),
+				worker_task,
+			);
+
+			// the miners never yield, so they run on threads of their own rather than as tasks.
+			for i in 0..threads {
+				let worker = worker.clone();
+				std::thread::Builder::new()
+					.name(format!("pow-miner-{i}"))
+					.spawn(move || pow::mine(worker))
+					.map_err(|e| ServiceError::Other(format!("Failed to start a miner: {e}")))?;
+			}
+		},
+		Consensus::Babe => {
+			let (block_import, babe_link, worker_handle) =
+				babe.expect("the babe parts are built for the babe consensus; qed");
+
+			// the babe worker of the import queue stops, and takes the node down with it, once
+			// its last handle is dropped.
+			task_manager.keep_alive(worker_handle);
+
+			if role.is_authority() {
+				let slot_duration = babe_link.config().slot_duration();
+				let babe = sc_consensus_babe::start_babe(sc_consensus_babe::BabeParams {
+					keystore: keystore_container.keystore(),
+					client,
+					select_chain,
+					env: proposer,
+					block_import,
+					sync_oracle: sync_service.clone(),
+					justification_sync_link: sync_service,
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+						let slot = sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+							*timestamp,
+							slot_duration,
+						);
+						Ok((slot, timestamp))
+					},
+					force_authoring,
+					backoff_authoring_blocks: Option::<()>::None,
+					babe_link,
+					block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+					max_block_proposal_slot_portion: None,
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+				})?;
+
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"babe-proposer",
+					Some("block-authoring"),
+					babe,
+				);
+			}
+		},
 	}
 
 	network_starter.start_network();
@@ -0,0 +1,261 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `upgrade` subcommand, which upgrades the runtime of a running chain to a compiled one.
+
+use crate::extrinsic;
+use frame::deps::{
+	codec::Decode,
+	frame_system,
+	sp_core::{
+		blake2_256,
+		crypto::{AccountId32, Pair as _},
+		sr25519,
+		traits::{RuntimeCode, WrappedRuntimeCode},
+		twox_128, Bytes,
+	},
+};
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
+use minimal_template_runtime::{interface, RuntimeCall};
+use sc_executor::{RuntimeVersionOf, WasmExecutor};
+use sp_api::RuntimeVersion;
+use std::{path::PathBuf, time::Duration};
+
+/// How long to wait for a submitted transaction to take effect.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Upgrade the runtime of a running chain to a compiled one.
+///
+/// The upgrade is authorized with `sudo(system.authorize_upgrade)` and then applied with
+/// `system.apply_authorized_upgrade`, both signed with the sudo key of the chain.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct UpgradeCmd {
+	/// The compiled runtime wasm to upgrade to, compressed or not.
+	#[arg(long)]
+	pub wasm: PathBuf,
+
+	/// The HTTP RPC endpoint of a node of the chain.
+	#[arg(long, default_value = "http://127.0.0.1:9944")]
+	pub url: String,
+
+	/// The secret URI of the sudo key.
+	#[arg(long, default_value = "//Alice")]
+	pub suri: String,
+
+	/// Accept a runtime with another `transaction_version`, which invalidates the transactions
+	/// signed for the current runtime.
+	#[arg(long)]
+	pub allow_transaction_version_change: bool,
+}
+
+impl UpgradeCmd {
+	/// Run the `upgrade` command.
+	pub async fn run(&self) -> sc_cli::Result<()> {
+		let code = std::fs::read(&self.wasm)?;
+		let new = runtime_version(&code)?;
+		let client = HttpClientBuilder::default().build(&self.url).map_err(rpc_error)?;
+		let on_chain: RuntimeVersion =
+			client.request("state_getRuntimeVersion", rpc_params![]).await.map_err(rpc_error)?;
+
+		check_upgrade(&on_chain, &new, self.allow_transaction_version_change)?;
+
+		let signer = Signer::new(&client, &self.suri, &on_chain).await?;
+		let sudo_key: Option<interface::AccountId> =
+			storage(&client, &[twox_128(b"Sudo"), twox_128(b"Key")].concat()).await?;
+		if sudo_key.as_ref() != Some(&signer.account) {
+			return Err(sc_cli::Error::Input(format!(
+				"{} is not the sudo key of the chain",
+				signer.account
+			)));
+		}
+
+		let code_hash: interface::Hash = blake2_256(&code).into();
+		let authorize = RuntimeCall::Sudo(pallet_sudo::Call::sudo {
+			call: Box::new(RuntimeCall::System(frame_system::Call::authorize_upgrade {
+				code_hash,
+			})),
+		});
+		signer.submit(&client, authorize, 0).await?;
+		let authorized_upgrade_key = [twox_128(b"System"), twox_128(b"AuthorizedUpgrade")].concat();
+		wait_until(|| async {
+			// the authorized code hash, and whether its version is checked.
+			let authorized: Option<(interface::Hash, bool)> =
+				storage(&client, &authorized_upgrade_key).await?;
+			Ok(authorized.is_some_and(|(hash, _)| hash == code_hash))
+		})
+		.await?;
+		log::info!("Authorized the upgrade to {code_hash:?}");
+
+		let apply = RuntimeCall::System(frame_system::Call::apply_authorized_upgrade { code });
+		signer.submit(&client, apply, 1).await?;
+		wait_until(|| async {
+			let version: RuntimeVersion = client
+				.request("state_getRuntimeVersion", rpc_params![])
+				.await
+				.map_err(rpc_error)?;
+			Ok(version.spec_version == new.spec_version)
+		})
+		.await?;
+		log::info!(
+			"Upgraded {} from spec_version {} to {}",
+			new.spec_name,
+			on_chain.spec_version,
+			new.spec_version
+		);
+		Ok(())
+	}
+}
+
+/// An error of an RPC request, as a command error.
+fn rpc_error(e: jsonrpsee::core::ClientError) -> sc_cli::Error {
+	sc_cli::Error::Application(Box::new(e))
+}
+
+/// The value at the storage `key` of the best block, if any.
+async fn storage<T: Decode>(client: &HttpClient, key: &[u8]) -> sc_cli::Result<Option<T>> {
+	let value: Option<Bytes> = client
+		.request("state_getStorage", rpc_params![Bytes(key.to_vec())])
+		.await
+		.map_err(rpc_error)?;
+	value
+		.map(|value| T::decode(&mut &value[..]))
+		.transpose()
+		.map_err(|e| sc_cli::Error::Application(Box::new(e)))
+}
+
+/// The version of the runtime `code`, as the chain reads it: from the version embedded in the
+/// wasm, or else by calling its `Core_version`.
+fn runtime_version(code: &[u8]) -> sc_cli::Result<RuntimeVersion> {
+	let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder().build();
+	let fetcher = WrappedRuntimeCode(code.into());
+	let runtime_code =
+		RuntimeCode { code_fetcher: &fetcher, heap_pages: None, hash: blake2_256(code).to_vec() };
+
+	let mut state = sp_io::TestExternalities::default();
+	executor
+		.runtime_version(&mut state.ext(), &runtime_code)
+		.map_err(|e| sc_cli::Error::Input(format!("Not a runtime wasm: {e}")))
+}
+
+/// Check that `new` is an upgrade of the `on_chain` runtime: the same runtime, in a later version,
+/// that keeps the `transaction_version` unless `allow_transaction_version_change`.
+fn check_upgrade(
+	on_chain: &RuntimeVersion,
+	new: &RuntimeVersion,
+	allow_transaction_version_change: bool,
+) -> sc_cli::Result<()> {
+	if new.spec_name != on_chain.spec_name {
+		return Err(sc_cli::Error::Input(format!(
+			"The wasm is a runtime of {}, not of the chain's {}",
+			new.spec_name, on_chain.spec_name
+		)));
+	}
+	if new.spec_version <= on_chain.spec_version {
+		return Err(sc_cli::Error::Input(format!(
+			"The wasm's spec_version {} must be above the chain's {}",
+			new.spec_version, on_chain.spec_version
+		)));
+	}
+	if new.transaction_version != on_chain.transaction_version {
+		if !allow_transaction_version_change {
+			return Err(sc_cli::Error::Input(format!(
+				"The wasm changes the transaction_version from {} to {}, which invalidates the \
+				 transactions signed for the chain's runtime. Pass \
+				 --allow-transaction-version-change to upgrade anyway",
+				on_chain.transaction_version, new.transaction_version
+			)));
+		}
+		log::warn!(
+			"transaction_version changes from {} to {}: transactions signed for the old runtime \
+			 will be invalid",
+			on_chain.transaction_version,
+			new.transaction_version
+		);
+	}
+	Ok(())
+}
+
+/// Poll `condition` until it holds, or fail after `TIMEOUT`.
+async fn wait_until<F, Fut>(condition: F) -> sc_cli::Result<()>
+where
+	F: Fn() -> Fut,
+	Fut: std::future::Future<Output = sc_cli::Result<bool>>,
+{
+	let start = std::time::Instant::now();
+	while start.elapsed() < TIMEOUT {
+		if condition().await? {
+			return Ok(());
+		}
+		futures_timer::Delay::new(Duration::from_millis(500)).await;
+	}
+	Err(sc_cli::Error::Application("Timed out waiting for the transaction to take effect".into()))
+}
+
+/// Signs transactions with a key, for the chain of a given runtime version.
+struct Signer {
+	pair: sr25519::Pair,
+	account: interface::AccountId,
+	/// The nonce of `account` before the first transaction.
+	nonce: interface::Nonce,
+	genesis_hash: interface::Hash,
+	version: RuntimeVersion,
+}
+
+impl Signer {
+	async fn new(
+		client: &HttpClient,
+		suri: &str,
+		version: &RuntimeVersion,
+	) -> sc_cli::Result<Self> {
+		let pair = sr25519::Pair::from_string(suri, None)
+			.map_err(|e| sc_cli::Error::Input(format!("Invalid secret URI: {e:?}")))?;
+		let account = AccountId32::from(pair.public());
+		let genesis_hash =
+			client.request("chain_getBlockHash", rpc_params![0]).await.map_err(rpc_error)?;
+		let mut signer = Self { pair, account, nonce: 0, genesis_hash, version: version.clone() };
+		signer.nonce = signer.nonce(client).await?;
+		Ok(signer)
+	}
+
+	/// The next nonce of the signer's account, including its pending transactions.
+	async fn nonce(&self, client: &HttpClient) -> sc_cli::Result<interface::Nonce> {
+		client
+			.request("system_accountNextIndex", rpc_params![self.account.clone()])
+			.await
+			.map_err(rpc_error)
+	}
+
+	/// Sign and submit `call` as the signer's transaction `index`, counted from the first.
+	async fn submit(
+		&self,
+		client: &HttpClient,
+		call: RuntimeCall,
+		index: u32,
+	) -> sc_cli::Result<()> {
+		let xt = extrinsic::signed(
+			&self.pair,
+			call,
+			self.nonce + index,
+			self.genesis_hash,
+			&self.version,
+		);
+		let _: interface::Hash =
+			client.request("author_submitExtrinsic", rpc_params![xt]).await.map_err(rpc_error)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,49 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The native token as reported by `chainMetadata_tokenInfo` on a `--dev` node.
+
+mod common;
+
+use frame::traits::Get;
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::HttpClientBuilder;
+use minimal_template_node::rpc::chain_metadata::TokenInfo;
+use minimal_template_runtime::{interface, SS58_PREFIX, TOKEN_DECIMALS, TOKEN_NAME, TOKEN_SYMBOL};
+
+#[tokio::test]
+async fn chain_metadata_rpc_reports_the_token() {
+	let (_node, url) = common::start_node(&["--consensus", "instant-seal"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	let expected = TokenInfo {
+		name: TOKEN_NAME.into(),
+		symbol: TOKEN_SYMBOL.into(),
+		decimals: TOKEN_DECIMALS,
+		existential_deposit: interface::MinimumBalance::get().into(),
+		ss58_prefix: SS58_PREFIX,
+	};
+	let info: TokenInfo = client.request("chainMetadata_tokenInfo", rpc_params![]).await.unwrap();
+	assert_eq!(info, expected);
+
+	// the same token is reported at a given block.
+	let genesis_hash: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![0]).await.unwrap();
+	let info: TokenInfo =
+		client.request("chainMetadata_tokenInfo", rpc_params![Some(genesis_hash)]).await.unwrap();
+	assert_eq!(info, expected);
+}
@@ -0,0 +1,82 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by the integration tests of the node binary.
+
+#![allow(dead_code)]
+
+use frame::deps::{frame_system, sp_core::Bytes};
+use minimal_template_node::extrinsic;
+use minimal_template_runtime::{interface, RuntimeCall, VERSION};
+use sp_keyring::AccountKeyring;
+use std::{
+	io::{BufRead, BufReader},
+	process::{Child, Command, Stdio},
+};
+
+/// A running node, killed when dropped.
+pub struct Node(Child);
+
+impl Drop for Node {
+	fn drop(&mut self) {
+		let _ = self.0.kill();
+		let _ = self.0.wait();
+	}
+}
+
+/// Start a `--dev` node with the extra `args`, returning it and the url of its RPC server.
+pub fn start_node(args: &[&str]) -> (Node, String) {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_minimal-template-node"))
+		.args(["--dev", "--tmp"])
+		.args(args)
+		.args(["--port", "0", "--rpc-port", "0", "--no-prometheus"])
+		.stderr(Stdio::piped())
+		.spawn()
+		.expect("the node binary is built for integration tests; qed");
+	let mut lines = BufReader::new(child.stderr.take().unwrap()).lines();
+	let node = Node(child);
+
+	let addr = lines
+		.by_ref()
+		.map_while(Result::ok)
+		.find_map(|line| {
+			let (_, addr) = line.split_once("Running JSON-RPC server: addr=")?;
+			addr.split(',').next().map(ToString::to_string)
+		})
+		.expect("the node logs the address of its RPC server");
+	// keep draining the logs, so that the node never blocks on a full pipe.
+	std::thread::spawn(move || lines.for_each(drop));
+
+	(node, format!("http://{addr}"))
+}
+
+/// Run a `--dev` node with the extra `args`, which must refuse to start, returning its stderr.
+pub fn refused_start(args: &[&str]) -> String {
+	let output = Command::new(env!("CARGO_BIN_EXE_minimal-template-node"))
+		.args(["--dev", "--tmp"])
+		.args(args)
+		.output()
+		.expect("the node binary is built for integration tests; qed");
+	assert!(!output.status.success(), "the node started with {args:?}");
+	String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// A remark of `len` bytes, signed by Alice with `nonce` for the chain of `genesis_hash`.
+pub fn signed_remark(len: usize, nonce: interface::Nonce, genesis_hash: interface::Hash) -> Bytes {
+	let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![0; len] });
+	extrinsic::signed(&AccountKeyring::Alice.pair(), call, nonce, genesis_hash, &VERSION)
+}
@@ -0,0 +1,66 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reproducible chains of `--dev` nodes sealing blocks with a `--genesis-timestamp`.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::HttpClientBuilder;
+use minimal_template_runtime::interface;
+use serde_json::Value;
+
+/// Seal `count` blocks on a fresh `manual-seal-rpc` node, returning their hashes.
+async fn seal_chain(count: usize) -> Vec<interface::Hash> {
+	let (_node, url) = common::start_node(&[
+		"--consensus",
+		"manual-seal-rpc",
+		"--genesis-timestamp",
+		"1700000000000",
+		"--timestamp-increment",
+		"1000",
+	]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	let mut hashes = Vec::new();
+	for _ in 0..count {
+		let created: Value = client
+			.request("engine_createBlock", rpc_params![true, true, Option::<interface::Hash>::None])
+			.await
+			.unwrap();
+		hashes.push(serde_json::from_value(created["hash"].clone()).unwrap());
+	}
+	hashes
+}
+
+#[tokio::test]
+async fn identical_inputs_seal_identical_chains() {
+	let first = seal_chain(3).await;
+	// wait, so that the system time would differ between the two runs.
+	tokio::time::sleep(std::time::Duration::from_millis(1_100)).await;
+	let second = seal_chain(3).await;
+	assert_eq!(first, second);
+}
+
+#[test]
+fn timestamp_options_are_refused_without_manual_or_instant_seal() {
+	for consensus in ["aura-grandpa", "babe", "pow-1"] {
+		let stderr = common::refused_start(&["--consensus", consensus, "--genesis-timestamp", "0"]);
+		assert!(stderr.contains("`--genesis-timestamp` only applies"), "{stderr}");
+	}
+	common::refused_start(&["--timestamp-increment", "1000"]);
+}
@@ -0,0 +1,73 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sealing and finalizing blocks on demand through the `engine_*` RPC methods of a `--dev`
+//! `manual-seal-rpc` node.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
+use minimal_template_runtime::interface;
+use serde_json::Value;
+
+/// Seal a new block on top of `parent_hash`, or the best block if omitted, returning its hash.
+async fn create_block(
+	client: &HttpClient,
+	parent_hash: Option<interface::Hash>,
+) -> interface::Hash {
+	let created: Value =
+		client.request("engine_createBlock", rpc_params![true, false, parent_hash]).await.unwrap();
+	serde_json::from_value(created["hash"].clone()).unwrap()
+}
+
+/// The hash of the finalized head.
+async fn finalized_head(client: &HttpClient) -> interface::Hash {
+	client.request("chain_getFinalizedHead", rpc_params![]).await.unwrap()
+}
+
+#[tokio::test]
+async fn engine_rpc_seals_forks_and_finalizes() {
+	let (_node, url) = common::start_node(&["--consensus", "manual-seal-rpc"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	let genesis_hash: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![0]).await.unwrap();
+
+	// without a block timer, nothing is sealed until asked for.
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+	let best: Value = client.request("chain_getHeader", rpc_params![]).await.unwrap();
+	assert_eq!(best["number"], "0x0");
+
+	let first = create_block(&client, None).await;
+	let second = create_block(&client, Some(first)).await;
+	// a sibling of `second`, forking off `first`.
+	let fork = create_block(&client, Some(first)).await;
+	assert_ne!(second, fork);
+
+	let fork_header: Value = client.request("chain_getHeader", rpc_params![fork]).await.unwrap();
+	assert_eq!(fork_header["number"], "0x2");
+
+	// blocks are only finalized on demand.
+	assert_eq!(finalized_head(&client).await, genesis_hash);
+	let finalized: bool = client
+		.request("engine_finalizeBlock", rpc_params![first, Option::<Vec<u8>>::None])
+		.await
+		.unwrap();
+	assert!(finalized);
+	assert_eq!(finalized_head(&client).await, first);
+}
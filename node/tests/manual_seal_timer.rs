@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Delayed finality and reorgs of the `manual-seal-<ms>` block timer of a `--dev` node.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
+use minimal_template_runtime::interface;
+use serde_json::Value;
+use std::time::Duration;
+
+/// The number of the block of `hash`, or of the best block if omitted.
+async fn block_number(client: &HttpClient, hash: Option<interface::Hash>) -> u64 {
+	let header: Value = client.request("chain_getHeader", rpc_params![hash]).await.unwrap();
+	u64::from_str_radix(header["number"].as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+/// The hash of the finalized head.
+async fn finalized_head(client: &HttpClient) -> interface::Hash {
+	client.request("chain_getFinalizedHead", rpc_params![]).await.unwrap()
+}
+
+#[tokio::test]
+async fn finality_lags_behind_the_best_block() {
+	let (_node, url) =
+		common::start_node(&["--consensus", "manual-seal-100", "--finality-delay", "3"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	tokio::time::sleep(Duration::from_secs(2)).await;
+	let finalized = block_number(&client, Some(finalized_head(&client).await)).await;
+	let best = block_number(&client, None).await;
+	assert!(finalized > 0);
+	assert!(best >= finalized + 3);
+}
+
+#[tokio::test]
+async fn forks_reorg_the_best_block() {
+	let (_node, url) = common::start_node(&[
+		"--consensus",
+		"manual-seal-200",
+		"--finality-delay",
+		"1",
+		"--fork-rate",
+		"100",
+	]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	tokio::time::sleep(Duration::from_secs(1)).await;
+	let number = block_number(&client, None).await;
+	let best: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![number]).await.unwrap();
+
+	// every tick replaces the best block with a sibling, and builds on top of it.
+	tokio::time::sleep(Duration::from_secs(1)).await;
+	let canonical: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![number]).await.unwrap();
+	assert_ne!(best, canonical);
+	assert!(block_number(&client, None).await > number);
+}
+
+#[test]
+fn fork_options_are_refused_without_a_block_timer() {
+	for consensus in ["instant-seal", "manual-seal-rpc", "aura-grandpa", "babe", "pow-1"] {
+		let stderr = common::refused_start(&["--consensus", consensus, "--finality-delay", "1"]);
+		assert!(stderr.contains("`--finality-delay` and `--fork-rate` only apply"), "{stderr}");
+	}
+	let stderr = common::refused_start(&["--consensus", "manual-seal-100", "--fork-rate", "50"]);
+	assert!(stderr.contains("requires a `--finality-delay`"), "{stderr}");
+}
@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sealing blocks only for ready transactions, or after an idle interval, on a `--dev` node.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
+use minimal_template_runtime::interface;
+use serde_json::Value;
+use std::time::Duration;
+
+/// The number of the best block.
+async fn best_number(client: &HttpClient) -> u64 {
+	let header: Value = client.request("chain_getHeader", rpc_params![]).await.unwrap();
+	u64::from_str_radix(header["number"].as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+#[tokio::test]
+async fn seals_only_for_ready_transactions() {
+	let (_node, url) = common::start_node(&["--consensus", "on-demand-seal-60000"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	// an idle node seals nothing.
+	tokio::time::sleep(Duration::from_secs(1)).await;
+	assert_eq!(best_number(&client).await, 0);
+
+	let genesis_hash: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![0]).await.unwrap();
+	let _: interface::Hash = client
+		.request("author_submitExtrinsic", rpc_params![common::signed_remark(10, 0, genesis_hash)])
+		.await
+		.unwrap();
+
+	tokio::time::sleep(Duration::from_secs(1)).await;
+	assert_eq!(best_number(&client).await, 1);
+	let pending: Vec<Value> =
+		client.request("author_pendingExtrinsics", rpc_params![]).await.unwrap();
+	assert!(pending.is_empty());
+}
+
+#[tokio::test]
+async fn seals_transactions_that_do_not_fit_into_one_block() {
+	let (_node, url) = common::start_node(&["--consensus", "on-demand-seal-60000"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	// only two of these fit into the 3.75 MiB of a block's normal transactions. The later nonces
+	// go first, so that all three become ready at once.
+	let genesis_hash: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![0]).await.unwrap();
+	for nonce in [2, 1, 0] {
+		let _: interface::Hash = client
+			.request(
+				"author_submitExtrinsic",
+				rpc_params![common::signed_remark(1_500_000, nonce, genesis_hash)],
+			)
+			.await
+			.unwrap();
+	}
+
+	// the last one is sealed into a second block, long before the idle time is over.
+	tokio::time::sleep(Duration::from_secs(3)).await;
+	assert_eq!(best_number(&client).await, 2);
+	let pending: Vec<Value> =
+		client.request("author_pendingExtrinsics", rpc_params![]).await.unwrap();
+	assert!(pending.is_empty());
+}
+
+#[tokio::test]
+async fn seals_empty_blocks_after_the_max_idle_time() {
+	let (_node, url) = common::start_node(&["--consensus", "on-demand-seal-200"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	tokio::time::sleep(Duration::from_secs(1)).await;
+	assert!(best_number(&client).await > 0);
+}
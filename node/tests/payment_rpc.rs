@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fee estimation through the `payment_*` RPC methods of a `--dev` instant-seal node.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::HttpClientBuilder;
+use minimal_template_runtime::interface;
+use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+
+#[tokio::test]
+async fn payment_rpc_estimates_fees() {
+	let (_node, url) = common::start_node(&["--consensus", "instant-seal"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	let genesis_hash: interface::Hash =
+		client.request("chain_getBlockHash", rpc_params![0]).await.unwrap();
+	let short = common::signed_remark(10, 0, genesis_hash);
+	let long = common::signed_remark(1_000, 0, genesis_hash);
+
+	let info: RuntimeDispatchInfo<interface::Balance> =
+		client.request("payment_queryInfo", rpc_params![short.clone()]).await.unwrap();
+	assert!(info.partial_fee > 0);
+
+	// the estimate is the sum of the fee details, and can be taken at a given block.
+	let details: FeeDetails<interface::Balance> = client
+		.request("payment_queryFeeDetails", rpc_params![short, Some(genesis_hash)])
+		.await
+		.unwrap();
+	let inclusion_fee = details.inclusion_fee.expect("signed transactions pay fees");
+	assert_eq!(
+		info.partial_fee,
+		inclusion_fee.base_fee + inclusion_fee.len_fee + inclusion_fee.adjusted_weight_fee
+	);
+
+	// longer transactions pay more.
+	let long_info: RuntimeDispatchInfo<interface::Balance> =
+		client.request("payment_queryInfo", rpc_params![long]).await.unwrap();
+	assert!(long_info.partial_fee > info.partial_fee);
+}
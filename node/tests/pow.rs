@@ -0,0 +1,41 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mining blocks on a `--dev` node with the `pow` consensus.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::HttpClientBuilder;
+use serde_json::Value;
+use std::time::Duration;
+
+#[tokio::test]
+async fn cpu_miner_mines_blocks() {
+	let (_node, url) = common::start_node(&["--consensus", "pow-1"]);
+	let client = HttpClientBuilder::default().build(url).unwrap();
+
+	// mining is random, so give the first block some time.
+	for _ in 0..60 {
+		let header: Value = client.request("chain_getHeader", rpc_params![]).await.unwrap();
+		if header["number"] != "0x0" {
+			return;
+		}
+		tokio::time::sleep(Duration::from_secs(1)).await;
+	}
+	panic!("no block was mined within a minute");
+}
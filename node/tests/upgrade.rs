@@ -0,0 +1,99 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `upgrade` subcommand against a `--dev` instant-seal node.
+
+mod common;
+
+use jsonrpsee::core::{client::ClientT, rpc_params};
+use jsonrpsee_http_client::HttpClientBuilder;
+use minimal_template_runtime::{wasm_spec_version_incremented, VERSION, WASM_BINARY};
+use sp_api::RuntimeVersion;
+use std::{
+	process::{Command, Output},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The wasm of the runtime that the node runs.
+fn current_wasm() -> &'static [u8] {
+	WASM_BINARY.expect("the runtime wasm is built for tests; qed")
+}
+
+/// The wasm of the same runtime built with the next `spec_version`.
+fn next_wasm() -> &'static [u8] {
+	wasm_spec_version_incremented::WASM_BINARY
+		.expect("the node's tests build the runtime with `spec-version-incremented-wasm`; qed")
+}
+
+/// Run the `upgrade` subcommand with `code` against the node at `url`, signed with `suri`.
+fn upgrade(url: &str, code: &[u8], suri: &str) -> Output {
+	static WASMS: AtomicUsize = AtomicUsize::new(0);
+	let wasm = std::env::temp_dir().join(format!(
+		"minimal-runtime-{}-{}.wasm",
+		std::process::id(),
+		WASMS.fetch_add(1, Ordering::Relaxed)
+	));
+	std::fs::write(&wasm, code).unwrap();
+
+	let output = Command::new(env!("CARGO_BIN_EXE_minimal-template-node"))
+		.arg("upgrade")
+		.arg("--wasm")
+		.arg(&wasm)
+		.args(["--url", url, "--suri", suri])
+		.output()
+		.unwrap();
+	let _ = std::fs::remove_file(&wasm);
+	output
+}
+
+#[test]
+fn upgrade_refuses_a_runtime_without_a_spec_version_bump() {
+	let (_node, url) = common::start_node(&["--consensus", "instant-seal"]);
+
+	let output = upgrade(&url, current_wasm(), "//Alice");
+
+	assert!(!output.status.success());
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.contains("spec_version 2 must be above the chain's 2"), "{stderr}");
+}
+
+#[test]
+fn upgrade_refuses_a_signer_other_than_the_sudo_key() {
+	let (_node, url) = common::start_node(&["--consensus", "instant-seal"]);
+
+	let output = upgrade(&url, next_wasm(), "//Bob");
+
+	assert!(!output.status.success());
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.contains("is not the sudo key of the chain"), "{stderr}");
+}
+
+#[tokio::test]
+async fn upgrade_applies_a_runtime_with_a_spec_version_bump() {
+	let (_node, url) = common::start_node(&["--consensus", "instant-seal"]);
+
+	let output = upgrade(&url, next_wasm(), "//Alice");
+
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(output.status.success(), "{stderr}");
+	assert!(stderr.contains("from spec_version 2 to 3"), "{stderr}");
+
+	let client = HttpClientBuilder::default().build(url).unwrap();
+	let version: RuntimeVersion =
+		client.request("state_getRuntimeVersion", rpc_params![]).await.unwrap();
+	assert_eq!(version.spec_version, VERSION.spec_version + 1);
+}
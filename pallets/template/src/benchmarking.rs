@@ -0,0 +1,36 @@
+//! Benchmarks of the claims in the template pallet.
+
+use crate::pallet::*;
+use frame::{deps::frame_benchmarking::v2::*, prelude::*};
+use frame_system::RawOrigin;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_claim() {
+        let caller: T::AccountId = whitelisted_caller();
+        let claim = T::Hash::default();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), claim);
+
+        assert!(Claims::<T>::contains_key(claim));
+    }
+
+    #[benchmark]
+    fn revoke_claim() {
+        let caller: T::AccountId = whitelisted_caller();
+        let claim = T::Hash::default();
+        Claims::<T>::insert(
+            claim,
+            (caller.clone(), frame_system::Pallet::<T>::block_number()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), claim);
+
+        assert!(!Claims::<T>::contains_key(claim));
+    }
+}
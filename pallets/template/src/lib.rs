@@ -9,9 +9,17 @@
 // Re-export all pallet parts, this is needed to properly import the pallet into the runtime.
 pub use pallet::*;
 
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[frame::pallet]
 pub mod pallet {
+    use crate::weights::WeightInfo;
     use frame::prelude::*;
+    #[cfg(feature = "try-runtime")]
+    use frame::deps::sp_runtime::TryRuntimeError;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -20,7 +28,8 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        // type WeightInfo;
+        /// The weights of this pallet's calls.
+        type WeightInfo: WeightInfo;
     }
 
     // Pallets use events to inform users when important changes are made.
@@ -47,12 +56,25 @@ pub mod pallet {
     pub type Claims<T: Config> =
         StorageMap<_, Blake2_128Concat, T::Hash, (T::AccountId, BlockNumberFor<T>)>;
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            // a claim cannot have been created after the block that is being checked.
+            ensure!(
+                Claims::<T>::iter_values().all(|(_, created)| created <= n),
+                "claim created in the future"
+            );
+            Ok(())
+        }
+    }
+
     // Dispatchable functions allow users to interact with the pallet and invoke state changes.
     // These functions materialize as "extrinsics", which are often compared to transactions.
     // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(Weight::default())]
+        #[pallet::weight(T::WeightInfo::create_claim())]
         #[pallet::call_index(0)]
         pub fn create_claim(origin: OriginFor<T>, claim: T::Hash) -> DispatchResult {
             // Check that the extrinsic was signed and get the signer.
@@ -73,7 +95,7 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(Weight::default())]
+        #[pallet::weight(T::WeightInfo::revoke_claim())]
         #[pallet::call_index(1)]
         pub fn revoke_claim(origin: OriginFor<T>, claim: T::Hash) -> DispatchResult {
             // Check that the extrinsic was signed and get the signer.
@@ -93,8 +115,3 @@ pub mod pallet {
         }
     }
 }
-
-pub mod weights {
-    // Placeholder struct for the pallet weights
-    pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
-}
@@ -0,0 +1,43 @@
+//! Weights of the template pallet.
+//!
+//! These are estimates: the storage items each call reads and writes, listed next to every
+//! function, plus a fixed execution time. Regenerate them on reference hardware with the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! ```sh
+//! minimal-template-node benchmark pallet --chain dev --pallet pallet_minimal_template \
+//!     --extrinsic '*' --output pallets/template/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+
+/// The weights of the calls of the pallet.
+pub trait WeightInfo {
+    fn create_claim() -> Weight;
+    fn revoke_claim() -> Weight;
+}
+
+/// The weights of the pallet, using the runtime's database weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// The claim.
+    fn create_claim() -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    /// The claim.
+    fn revoke_claim() -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn create_claim() -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn revoke_claim() -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+}
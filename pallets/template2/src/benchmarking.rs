@@ -0,0 +1,260 @@
+//! Benchmarks of the template2 currency, in the worst case of every call: accounts and histories
+//! that are created rather than updated, and schedules that stay partly locked.
+
+use crate::{pallet::*, treasury::SpendProposal, vesting::VestingInfo};
+use alloc::vec::Vec;
+use frame::{
+    arithmetic::{One, Saturating, Zero},
+    deps::frame_benchmarking::v2::*,
+    prelude::*,
+    traits::SortedMembers,
+};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// An amount that can be moved around many times without dropping below the existential deposit.
+fn amount<T: Config>() -> Balance {
+    T::ExistentialDeposit::get().max(1).saturating_mul(100)
+}
+
+/// `who`, credited with plenty of `amount`s.
+fn funded<T: Config>(who: T::AccountId) -> Result<T::AccountId, BenchmarkError> {
+    Pallet::<T>::mutate_balances(None, Some(&who), amount::<T>().saturating_mul(1_000))?;
+    Ok(who)
+}
+
+fn next_block<T: Config>() -> BlockNumberFor<T> {
+    frame_system::Pallet::<T>::block_number().saturating_add(One::one())
+}
+
+/// The council, if it has enough members to pay out a spend.
+fn council<T: Config>() -> Result<Vec<T::AccountId>, BenchmarkError> {
+    let council = T::Council::sorted_members();
+    if council.len() < T::SpendThreshold::get().max(1) as usize {
+        return Err(BenchmarkError::Stop(
+            "the council is too small to pay out a spend",
+        ));
+    }
+    Ok(council)
+}
+
+/// Store the spend proposal 0 of `amount` to `beneficiary`, with its bond in the bond account.
+fn bonded_proposal<T: Config>(
+    proposer: T::AccountId,
+    beneficiary: T::AccountId,
+    approvals: Vec<T::AccountId>,
+) -> Result<(), BenchmarkError> {
+    let bond = T::SpendProposalBond::get();
+    if !bond.is_zero() {
+        Pallet::<T>::mutate_balances(None, Some(&Pallet::<T>::bond_account()), bond)?;
+    }
+    SpendProposals::<T>::insert(
+        0,
+        SpendProposal {
+            proposer,
+            beneficiary,
+            amount: amount::<T>(),
+            bond,
+            approvals: approvals
+                .try_into()
+                .map_err(|_| BenchmarkError::Stop("more approvals than the threshold"))?,
+        },
+    );
+    NextProposalId::<T>::put(1);
+    Ok(())
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn mint_unsafe() -> Result<(), BenchmarkError> {
+        let origin =
+            T::MintOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, dest.clone(), amount::<T>());
+
+        assert_eq!(Balances::<T>::get(&dest), Some(amount::<T>()));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn transfer() -> Result<(), BenchmarkError> {
+        let caller = funded::<T>(whitelisted_caller())?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), dest.clone(), amount::<T>());
+
+        assert!(Balances::<T>::contains_key(&dest));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn vested_transfer() -> Result<(), BenchmarkError> {
+        let caller = funded::<T>(whitelisted_caller())?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let schedule = VestingInfo::new(amount::<T>(), 1, next_block::<T>());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), dest.clone(), schedule);
+
+        // all of it, but the transfer fee, is locked.
+        assert!(Locks::<T>::get(&dest) > Some(0));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn vest() -> Result<(), BenchmarkError> {
+        let funder = funded::<T>(account("funder", 0, SEED))?;
+        let caller: T::AccountId = whitelisted_caller();
+        let schedule = VestingInfo::new(amount::<T>(), 1, next_block::<T>());
+        Pallet::<T>::vested_transfer(RawOrigin::Signed(funder).into(), caller.clone(), schedule)?;
+        frame_system::Pallet::<T>::set_block_number(next_block::<T>().saturating_add(One::one()));
+        let locked = Vesting::<T>::get(&caller).map(|schedule| schedule.locked);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert_eq!(Locks::<T>::get(&caller), locked.map(|locked| locked - 1));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn schedule_transfer() -> Result<(), BenchmarkError> {
+        let caller = funded::<T>(whitelisted_caller())?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+        // the call runs at block 1 at the earliest, so that it deposits events.
+        frame_system::Pallet::<T>::set_block_number(One::one());
+        let start = next_block::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            dest,
+            amount::<T>(),
+            start,
+            One::one(),
+            2,
+        );
+
+        assert_eq!(Agenda::<T>::get(start).len(), 1);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_scheduled_transfer() -> Result<(), BenchmarkError> {
+        let caller = funded::<T>(whitelisted_caller())?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+        Pallet::<T>::schedule_transfer(
+            RawOrigin::Signed(caller.clone()).into(),
+            dest,
+            amount::<T>(),
+            next_block::<T>(),
+            One::one(),
+            2,
+        )?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+
+        assert!(!ScheduledTransfers::<T>::contains_key(0));
+        Ok(())
+    }
+
+    /// Proposing a spend, paid out right away if one approval is enough.
+    #[benchmark]
+    fn propose_spend() -> Result<(), BenchmarkError> {
+        let proposer = funded::<T>(council::<T>()?[0].clone())?;
+        funded::<T>(Pallet::<T>::treasury_account())?;
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(proposer), beneficiary, amount::<T>());
+
+        assert_eq!(NextProposalId::<T>::get(), 1);
+        Ok(())
+    }
+
+    /// The approval that reaches the threshold, pays the spend out to a new account and returns
+    /// the bond to a reaped proposer.
+    #[benchmark]
+    fn approve_spend() -> Result<(), BenchmarkError> {
+        let council = council::<T>()?;
+        let threshold = T::SpendThreshold::get().max(1) as usize;
+        funded::<T>(Pallet::<T>::treasury_account())?;
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        bonded_proposal::<T>(
+            council[0].clone(),
+            beneficiary.clone(),
+            council[..threshold - 1].to_vec(),
+        )?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(council[threshold - 1].clone()), 0);
+
+        assert!(!SpendProposals::<T>::contains_key(0));
+        assert_eq!(Balances::<T>::get(&beneficiary), Some(amount::<T>()));
+        Ok(())
+    }
+
+    /// Rejecting a proposal, which moves its bond to an empty treasury.
+    #[benchmark]
+    fn reject_spend() -> Result<(), BenchmarkError> {
+        let origin =
+            T::RejectOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        bonded_proposal::<T>(
+            account("proposer", 0, SEED),
+            account("beneficiary", 0, SEED),
+            Vec::new(),
+        )?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 0);
+
+        assert!(!SpendProposals::<T>::contains_key(0));
+        Ok(())
+    }
+
+    /// Executing `n` standing orders that pay new accounts, and re-queueing them.
+    #[benchmark]
+    fn on_initialize(
+        n: Linear<0, { T::MaxScheduledPerBlock::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let start = next_block::<T>();
+        for i in 0..n {
+            // one order per payer, as each can only have a few open.
+            let caller = funded::<T>(account("caller", i, SEED))?;
+            Pallet::<T>::schedule_transfer(
+                RawOrigin::Signed(caller).into(),
+                account("dest", i, SEED),
+                amount::<T>(),
+                start,
+                One::one(),
+                2,
+            )?;
+        }
+        frame_system::Pallet::<T>::set_block_number(start);
+
+        #[block]
+        {
+            Pallet::<T>::on_initialize(start);
+        }
+
+        assert_eq!(
+            Agenda::<T>::get(start.saturating_add(One::one())).len(),
+            n as usize
+        );
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        frame::testing_prelude::TestState::new_empty(),
+        crate::pallet::tests::Runtime
+    );
+}
@@ -0,0 +1,51 @@
+//! Bounded balance history of the template2 currency, used for snapshot queries.
+
+use crate::pallet::{Balance, Config};
+use frame::prelude::*;
+
+/// The last `T::MaxCheckpoints` values of a balance, as `(block, value at the end of block)`,
+/// oldest first.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    DefaultNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Checkpoints<T: Config> {
+    /// The retained checkpoints, at most one per block.
+    pub history: BoundedVec<(BlockNumberFor<T>, Balance), T::MaxCheckpoints>,
+    /// Whether older checkpoints have been dropped to make room for newer ones.
+    pub pruned: bool,
+}
+
+impl<T: Config> Checkpoints<T> {
+    /// Record `value` as of block `now`, dropping the oldest checkpoint if there is no room.
+    pub fn record(&mut self, now: BlockNumberFor<T>, value: Balance) {
+        if let Some(last) = self.history.last_mut().filter(|(n, _)| *n == now) {
+            last.1 = value;
+            return;
+        }
+
+        let len = self.history.len();
+        if let Ok(Some(_)) = self.history.force_insert_keep_right(len, (now, value)) {
+            self.pruned = true;
+        }
+    }
+
+    /// The value at the end of block `n`, or `None` if that is older than the retained history.
+    pub fn at(&self, n: BlockNumberFor<T>) -> Option<Balance> {
+        match self.history.partition_point(|(block, _)| *block <= n) {
+            0 if self.pruned => None,
+            // nothing was ever recorded before `n`.
+            0 => Some(0),
+            i => Some(self.history[i - 1].1),
+        }
+    }
+}
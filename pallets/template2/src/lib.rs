@@ -2,9 +2,20 @@
 
 // use frame::prelude::*;
 
+extern crate alloc;
+
 // Re-export all pallet parts, this is needed to properly import the pallet into the runtime.
 pub use pallet::*;
 
+pub mod checkpoints;
+pub mod scheduled;
+pub mod treasury;
+pub mod vesting;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 // #[frame::pallet]
 // pub mod pallet {
 //     use super::*;
@@ -16,14 +27,88 @@ pub use pallet::*;
 //     pub struct Pallet<T>(_);
 // }
 
-#[frame::pallet(dev_mode)]
+#[frame::pallet]
 pub mod pallet {
-    use frame::prelude::*;
+    use crate::{
+        checkpoints::Checkpoints, scheduled::ScheduledTransfer, treasury::SpendProposal,
+        vesting::VestingInfo, weights::WeightInfo,
+    };
+    use alloc::vec::Vec;
+    use frame::{
+        arithmetic::{ArithmeticError, Permill, SaturatedConversion, Saturating, Zero},
+        deps::{frame_support::PalletId, sp_io::hashing::blake2_256},
+        prelude::*,
+        traits::{AccountIdConversion, SortedMembers, TrailingZeroInput},
+    };
+    #[cfg(any(test, feature = "try-runtime"))]
+    use frame::deps::sp_runtime::TryRuntimeError;
 
     pub type Balance = u128;
 
     #[pallet::config]
-    pub trait Config: frame_system::Config {}
+    pub trait Config: frame_system::Config {
+        /// The overarching event type of the runtime.
+        type RuntimeEvent: From<Event<Self>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>
+            + TryInto<Event<Self>>;
+
+        /// The minimum amount needed to create a new `Balances` entry.
+        ///
+        /// Every entry holds a provider reference on its account, so this is what it costs to
+        /// bloat the state with a new account.
+        #[pallet::constant]
+        type ExistentialDeposit: Get<Balance>;
+
+        /// The maximum number of scheduled transfers executed in a single block.
+        #[pallet::constant]
+        type MaxScheduledPerBlock: Get<u32>;
+
+        /// The maximum number of standing orders an account can have open at once.
+        #[pallet::constant]
+        type MaxScheduledPerAccount: Get<u32>;
+
+        /// The pallet's id, used to derive the treasury account.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The fraction of every transfer that is paid to the treasury as a fee.
+        #[pallet::constant]
+        type TransferFee: Get<Permill>;
+
+        /// The council members that can propose and approve spends out of the treasury.
+        type Council: SortedMembers<Self::AccountId>;
+
+        /// The number of council approvals needed to pay out a spend.
+        #[pallet::constant]
+        type SpendThreshold: Get<u32>;
+
+        /// What a council member puts up to propose a spend. It is returned once the spend is
+        /// paid out, and moved to the treasury if the proposal is rejected.
+        ///
+        /// Either zero or at least `ExistentialDeposit`, so it can always be returned.
+        #[pallet::constant]
+        type SpendProposalBond: Get<Balance>;
+
+        /// The maximum number of spend proposals waiting for approvals at once.
+        #[pallet::constant]
+        type MaxSpendProposals: Get<u32>;
+
+        /// The origin that can reject a spend proposal with `reject_spend`.
+        type RejectOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The number of checkpoints kept in each balance history, and in the issuance history.
+        #[pallet::constant]
+        type MaxCheckpoints: Get<u32>;
+
+        /// The origin that can mint new funds with `mint_unsafe`.
+        type MintOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The weights of this pallet's calls and hooks.
+        type WeightInfo: WeightInfo;
+    }
+
+    pub type ScheduledTransferOf<T> =
+        ScheduledTransfer<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -34,45 +119,741 @@ pub mod pallet {
 
     /// A mapping from `T::AccountId` to `Balance`
     #[pallet::storage]
-    pub type Balances<T: Config> = StorageMap<_, _, T::AccountId, Balance>;
+    pub type Balances<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance>;
+
+    /// The values an account's balance had at the end of its most recently changed blocks.
+    ///
+    /// Removed with the account's `Balances` entry, so a reaped account leaves nothing behind.
+    #[pallet::storage]
+    pub type BalanceHistory<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Checkpoints<T>, ValueQuery>;
+
+    /// The values of `TotalIssuance` at the end of its most recently changed blocks.
+    #[pallet::storage]
+    pub type IssuanceHistory<T: Config> = StorageValue<_, Checkpoints<T>, ValueQuery>;
+
+    /// The vesting schedule of an account, if it has one.
+    #[pallet::storage]
+    pub type Vesting<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, VestingInfo<BlockNumberFor<T>>>;
+
+    /// The part of an account's balance that is locked by vesting and cannot be transferred.
+    ///
+    /// Only updated when the schedule is created and on `vest`.
+    #[pallet::storage]
+    pub type Locks<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance>;
+
+    /// Standing orders, by the id they were given in `schedule_transfer`.
+    #[pallet::storage]
+    pub type ScheduledTransfers<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, ScheduledTransferOf<T>>;
+
+    /// The ids of the standing orders to execute at a given block.
+    #[pallet::storage]
+    pub type Agenda<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BlockNumberFor<T>,
+        BoundedVec<u32, T::MaxScheduledPerBlock>,
+        ValueQuery,
+    >;
+
+    /// The id of the next standing order.
+    #[pallet::storage]
+    pub type NextScheduleId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// The number of standing orders that an account has open.
+    #[pallet::storage]
+    pub type ScheduleCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Treasury spends that are waiting for council approvals, at most `T::MaxSpendProposals`.
+    #[pallet::storage]
+    pub type SpendProposals<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, u32, SpendProposal<T>>;
+
+    /// The id of the next spend proposal.
+    #[pallet::storage]
+    pub type NextProposalId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A standing order has been created.
+        TransferScheduled {
+            id: u32,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: Balance,
+            start: BlockNumberFor<T>,
+        },
+        /// A standing order has paid out.
+        ScheduledTransferExecuted {
+            id: u32,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: Balance,
+        },
+        /// A standing order could not pay out, because its transfer failed with `error`. It
+        /// still counts as one of its executions.
+        ///
+        /// The errors of this pallet are plain strings, which are not encoded: they are stored as
+        /// an empty `DispatchError::Other`.
+        ScheduledTransferFailed {
+            id: u32,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: Balance,
+            error: DispatchError,
+        },
+        /// A standing order was cancelled by its creator, or dropped because the agenda of its
+        /// next execution was full.
+        ScheduledTransferCancelled { id: u32 },
+        /// A council member proposed a spend out of the treasury.
+        SpendProposed {
+            id: u32,
+            proposer: T::AccountId,
+            beneficiary: T::AccountId,
+            amount: Balance,
+        },
+        /// A council member approved a spend.
+        SpendApproved { id: u32, who: T::AccountId },
+        /// A spend reached enough approvals and was paid out of the treasury.
+        SpendPaid {
+            id: u32,
+            beneficiary: T::AccountId,
+            amount: Balance,
+        },
+        /// A spend proposal was rejected, and its bond moved to the treasury.
+        SpendRejected { id: u32, slashed: Balance },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let agenda = Agenda::<T>::take(n);
+            let executed = agenda.len() as u32;
+            for id in agenda {
+                Self::execute_scheduled(id, n);
+            }
+
+            T::WeightInfo::on_initialize(executed)
+        }
+
+        fn integrity_test() {
+            let bond = T::SpendProposalBond::get();
+            assert!(
+                bond.is_zero() || bond >= T::ExistentialDeposit::get(),
+                "a spend proposal bond below the existential deposit may not be returnable"
+            );
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
+    /// A vesting schedule in the genesis config, as `(who, begin, length, liquid)`.
+    pub type GenesisVesting<T> = (
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        BlockNumberFor<T>,
+        Balance,
+    );
+
+    #[pallet::genesis_config]
+    #[derive(DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Initial balances of accounts.
+        pub balances: Vec<(T::AccountId, Balance)>,
+        /// Vesting schedules as `(who, begin, length, liquid)`. Everything but `liquid` of
+        /// `who`'s genesis balance unlocks linearly over `length` blocks from `begin`.
+        pub vesting: Vec<GenesisVesting<T>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (who, amount) in &self.balances {
+                assert!(
+                    !Balances::<T>::contains_key(who),
+                    "duplicate balance in genesis"
+                );
+                Pallet::<T>::mutate_balances(None, Some(who), *amount)
+                    .expect("genesis balances must not overflow the total issuance");
+            }
+
+            for (who, begin, length, liquid) in &self.vesting {
+                let balance =
+                    Balances::<T>::get(who).expect("vesting account must have a genesis balance");
+                let locked = balance.saturating_sub(*liquid);
+                let length = (*length).saturated_into::<Balance>().max(1);
+                let schedule = VestingInfo::new(locked, (locked / length).max(1), *begin);
+                assert!(schedule.is_valid(), "invalid vesting schedule in genesis");
+
+                Pallet::<T>::add_vesting_schedule(who, schedule)
+                    .expect("vesting account must be able to hold a consumer reference");
+            }
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// An unsafe mint that can be called by anyone. Not a great idea.
+        /// Mint `amount` out of thin air into `dest`. Nothing backs the new funds, so only
+        /// `T::MintOrigin` can call it.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::mint_unsafe())]
         pub fn mint_unsafe(
             origin: T::RuntimeOrigin,
             dest: T::AccountId,
             amount: Balance,
         ) -> DispatchResult {
-            // ensure that this is a signed account, but we don't really check `_anyone`.
-            let _anyone = ensure_signed(origin)?;
-
-            // update the balances map. Notice how all `<T: Config>` remains as `<T>`.
-            Balances::<T>::mutate(dest, |b| *b = Some(b.unwrap_or(0) + amount));
-            // update total issuance.
-            TotalIssuance::<T>::mutate(|t| *t = Some(t.unwrap_or(0) + amount));
+            T::MintOrigin::ensure_origin(origin)?;
 
-            Ok(())
+            // credit `dest` out of thin air, which also updates the total issuance.
+            Self::mutate_balances(None, Some(&dest), amount)
         }
 
         /// Transfer `amount` from `origin` to `dest`.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::transfer())]
         pub fn transfer(
             origin: T::RuntimeOrigin,
             dest: T::AccountId,
             amount: Balance,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
+            Self::transfer_with_fee(&sender, &dest, amount).map(|_| ())
+        }
+
+        /// Transfer `schedule.locked` from `origin` to `dest`, locked under `schedule`.
+        ///
+        /// Like any transfer it pays `T::TransferFee` to the treasury, so `dest` receives, and has
+        /// locked, `schedule.locked` minus the fee. `dest` must not already have a vesting
+        /// schedule.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::vested_transfer())]
+        pub fn vested_transfer(
+            origin: T::RuntimeOrigin,
+            dest: T::AccountId,
+            schedule: VestingInfo<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(schedule.is_valid(), "InvalidScheduleParams");
+            ensure!(
+                !Vesting::<T>::contains_key(&dest),
+                "ExistingVestingSchedule"
+            );
 
-            // ensure sender has enough balance, and if so, calculate what is left after `amount`.
-            let sender_balance = Balances::<T>::get(&sender).ok_or("NonExistentAccount")?;
-            if sender_balance < amount {
-                return Err("InsufficientBalance".into());
+            let locked = Self::transfer_with_fee(&sender, &dest, schedule.locked)?;
+            let schedule = VestingInfo { locked, ..schedule };
+            ensure!(schedule.is_valid(), "InvalidScheduleParams");
+            Self::add_vesting_schedule(&dest, schedule)
+        }
+
+        /// Unlock the portion of `origin`'s vesting schedule that has matured so far.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::vest())]
+        pub fn vest(origin: T::RuntimeOrigin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Vesting::<T>::contains_key(&who), "NotVesting");
+
+            Self::update_lock(&who);
+
+            Ok(())
+        }
+
+        /// Pay `amount` from `origin` to `dest` at block `start`, and then every `period` blocks
+        /// until it has been paid `count` times.
+        ///
+        /// `origin` must be able to afford `amount` out of its unlocked funds right now, and can
+        /// have at most `T::MaxScheduledPerAccount` orders open, so that filling the agendas
+        /// costs real funds.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::schedule_transfer())]
+        pub fn schedule_transfer(
+            origin: T::RuntimeOrigin,
+            dest: T::AccountId,
+            amount: Balance,
+            start: BlockNumberFor<T>,
+            period: BlockNumberFor<T>,
+            count: u32,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            ensure!(
+                count > 0 && (count == 1 || !period.is_zero()),
+                "InvalidSchedule"
+            );
+            ensure!(
+                start > frame_system::Pallet::<T>::block_number(),
+                "ScheduleInPast"
+            );
+            ensure!(
+                Self::free_balance(&from) >= amount,
+                "InsufficientBalance"
+            );
+            ScheduleCount::<T>::try_mutate(&from, |open| -> DispatchResult {
+                ensure!(
+                    *open < T::MaxScheduledPerAccount::get(),
+                    "TooManySchedules"
+                );
+                *open += 1;
+                Ok(())
+            })?;
+
+            let id = NextScheduleId::<T>::get();
+            NextScheduleId::<T>::put(id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+            Agenda::<T>::try_append(start, id).map_err(|_| "AgendaFull")?;
+            ScheduledTransfers::<T>::insert(
+                id,
+                ScheduledTransfer {
+                    from: from.clone(),
+                    dest: dest.clone(),
+                    amount,
+                    period,
+                    remaining: count,
+                    next: start,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::TransferScheduled {
+                id,
+                from,
+                to: dest,
+                amount,
+                start,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel the standing order `id`, which must have been created by `origin`.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::cancel_scheduled_transfer())]
+        pub fn cancel_scheduled_transfer(origin: T::RuntimeOrigin, id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let order = ScheduledTransfers::<T>::get(id).ok_or("UnknownSchedule")?;
+            ensure!(order.from == who, "NotScheduleOwner");
+
+            Self::remove_scheduled(id, &who);
+            Agenda::<T>::mutate(order.next, |agenda| agenda.retain(|x| *x != id));
+
+            Self::deposit_event(Event::<T>::ScheduledTransferCancelled { id });
+
+            Ok(())
+        }
+
+        /// Propose to pay `amount` out of the treasury to `beneficiary`. `origin` must be a
+        /// council member, puts up `T::SpendProposalBond`, and counts as the first approval.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::propose_spend())]
+        pub fn propose_spend(
+            origin: T::RuntimeOrigin,
+            beneficiary: T::AccountId,
+            amount: Balance,
+        ) -> DispatchResult {
+            let proposer = ensure_signed(origin)?;
+            ensure!(T::Council::contains(&proposer), "NotCouncilMember");
+            ensure!(
+                SpendProposals::<T>::count() < T::MaxSpendProposals::get(),
+                "TooManyProposals"
+            );
+
+            let bond = T::SpendProposalBond::get();
+            if !bond.is_zero() {
+                Self::mutate_balances(Some(&proposer), Some(&Self::bond_account()), bond)?;
             }
-            let reminder = sender_balance - amount;
+            let id = NextProposalId::<T>::get();
+            NextProposalId::<T>::put(id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+            SpendProposals::<T>::insert(
+                id,
+                SpendProposal {
+                    proposer: proposer.clone(),
+                    beneficiary: beneficiary.clone(),
+                    amount,
+                    bond,
+                    approvals: Default::default(),
+                },
+            );
+
+            Self::deposit_event(Event::<T>::SpendProposed {
+                id,
+                proposer: proposer.clone(),
+                beneficiary,
+                amount,
+            });
+
+            Self::approve(id, proposer)
+        }
+
+        /// Approve the spend proposal `id`. The approval that reaches `T::SpendThreshold` pays
+        /// it out, and fails if the treasury cannot afford it.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::approve_spend())]
+        pub fn approve_spend(origin: T::RuntimeOrigin, id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(T::Council::contains(&who), "NotCouncilMember");
+
+            Self::approve(id, who)
+        }
+
+        /// Reject the spend proposal `id`, moving its bond to the treasury.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::reject_spend())]
+        pub fn reject_spend(origin: T::RuntimeOrigin, id: u32) -> DispatchResult {
+            T::RejectOrigin::ensure_origin(origin)?;
+
+            let proposal = SpendProposals::<T>::take(id).ok_or("UnknownProposal")?;
+            if !proposal.bond.is_zero() {
+                Self::mutate_balances(
+                    Some(&Self::bond_account()),
+                    Some(&Self::treasury_account()),
+                    proposal.bond,
+                )?;
+            }
+            Self::deposit_event(Event::<T>::SpendRejected {
+                id,
+                slashed: proposal.bond,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Move `amount` from `from` to `to`. This is the only place where `Balances` and
+        /// `TotalIssuance` are written to, and it records a checkpoint of every value it changes
+        /// in `BalanceHistory` and `IssuanceHistory`.
+        ///
+        /// `from: None` mints `amount` and `to: None` burns it, so that the sum of all balances
+        /// always equals the total issuance. `from == to` is checked like any other transfer, but
+        /// leaves storage untouched. Either everything is applied or, on error, nothing is.
+        ///
+        /// Creating a `Balances` entry requires at least `T::ExistentialDeposit` and adds a
+        /// provider reference to the account. An entry whose balance drops to zero is removed
+        /// again, together with its history, dropping that reference. The treasury and the bond
+        /// account are exempt from the minimum, as they are credited with fees and bonds smaller
+        /// than that.
+        pub(crate) fn mutate_balances(
+            from: Option<&T::AccountId>,
+            to: Option<&T::AccountId>,
+            amount: Balance,
+        ) -> DispatchResult {
+            storage::with_storage_layer(|| {
+                match from {
+                    Some(from) => {
+                        let balance = Balances::<T>::get(from).ok_or("NonExistentAccount")?;
+                        let reminder = balance.checked_sub(amount).ok_or("InsufficientBalance")?;
+                        // funds still locked by vesting must stay with the sender.
+                        ensure!(
+                            reminder >= Locks::<T>::get(from).unwrap_or(0),
+                            "LiquidityRestrictions"
+                        );
+
+                        if to == Some(from) {
+                            return Ok(());
+                        }
+
+                        if reminder.is_zero() {
+                            Balances::<T>::remove(from);
+                            frame_system::Pallet::<T>::dec_providers(from)?;
+                        } else {
+                            Balances::<T>::insert(from, reminder);
+                        }
+                    }
+                    None => TotalIssuance::<T>::try_mutate(|t| -> DispatchResult {
+                        *t = Some(
+                            t.unwrap_or(0)
+                                .checked_add(amount)
+                                .ok_or(ArithmeticError::Overflow)?,
+                        );
+                        Ok(())
+                    })?,
+                }
+
+                match to {
+                    Some(to) => Balances::<T>::try_mutate(to, |b| -> DispatchResult {
+                        if b.is_none() {
+                            ensure!(
+                                amount >= T::ExistentialDeposit::get()
+                                    || *to == Self::treasury_account()
+                                    || *to == Self::bond_account(),
+                                "ExistentialDeposit"
+                            );
+                            frame_system::Pallet::<T>::inc_providers(to);
+                        }
+                        *b = Some(
+                            b.unwrap_or(0)
+                                .checked_add(amount)
+                                .ok_or(ArithmeticError::Overflow)?,
+                        );
+                        Ok(())
+                    })?,
+                    None => TotalIssuance::<T>::try_mutate(|t| -> DispatchResult {
+                        *t = Some(
+                            t.unwrap_or(0)
+                                .checked_sub(amount)
+                                .ok_or(ArithmeticError::Underflow)?,
+                        );
+                        Ok(())
+                    })?,
+                }
+
+                let now = frame_system::Pallet::<T>::block_number();
+                for who in [from, to].into_iter().flatten() {
+                    match Balances::<T>::get(who) {
+                        Some(balance) => {
+                            BalanceHistory::<T>::mutate(who, |history| history.record(now, balance))
+                        }
+                        None => BalanceHistory::<T>::remove(who),
+                    }
+                }
+                if from.is_none() || to.is_none() {
+                    let issuance = TotalIssuance::<T>::get().unwrap_or(0);
+                    IssuanceHistory::<T>::mutate(|history| history.record(now, issuance));
+                }
+
+                Ok(())
+            })
+        }
+
+        /// `who`'s balance at the end of block `n`.
+        ///
+        /// `None` if `n` is in the future, or older than the checkpoints kept for `who`. The
+        /// history of a reaped account is removed with it, so its balance reads as zero at every
+        /// block before.
+        pub fn balance_at(who: &T::AccountId, n: BlockNumberFor<T>) -> Option<Balance> {
+            if n > frame_system::Pallet::<T>::block_number() {
+                return None;
+            }
+            BalanceHistory::<T>::get(who).at(n)
+        }
+
+        /// The total issuance at the end of block `n`.
+        ///
+        /// `None` if `n` is in the future, or older than the checkpoints kept for the issuance.
+        pub fn total_issuance_at(n: BlockNumberFor<T>) -> Option<Balance> {
+            if n > frame_system::Pallet::<T>::block_number() {
+                return None;
+            }
+            IssuanceHistory::<T>::get().at(n)
+        }
+
+        /// The account of the treasury, which collects transfer fees.
+        pub fn treasury_account() -> T::AccountId {
+            T::PalletId::get().into_account_truncating()
+        }
+
+        /// The account that holds the bonds of open spend proposals.
+        ///
+        /// Hashed rather than a truncated sub-account of the treasury's, which could be the
+        /// treasury account itself with short account ids.
+        pub fn bond_account() -> T::AccountId {
+            let entropy = (T::PalletId::get(), b"bond").using_encoded(blake2_256);
+            Decode::decode(&mut TrailingZeroInput::new(&entropy))
+                .expect("infinite length input; no invalid inputs for type; qed")
+        }
+
+        /// Transfer `amount` from `from` to `to`, of which `T::TransferFee` goes to the treasury.
+        /// Returns what `to` received.
+        fn transfer_with_fee(
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: Balance,
+        ) -> Result<Balance, DispatchError> {
+            storage::with_storage_layer(|| {
+                let fee = T::TransferFee::get() * amount;
+                if !fee.is_zero() {
+                    Self::mutate_balances(Some(from), Some(&Self::treasury_account()), fee)?;
+                }
+                Self::mutate_balances(Some(from), Some(to), amount - fee)?;
+                Ok(amount - fee)
+            })
+        }
+
+        /// Record `who`'s approval of the spend proposal `id`, paying it out once it has enough.
+        fn approve(id: u32, who: T::AccountId) -> DispatchResult {
+            let mut proposal = SpendProposals::<T>::get(id).ok_or("UnknownProposal")?;
+            ensure!(!proposal.approvals.contains(&who), "AlreadyApproved");
+            Self::deposit_event(Event::<T>::SpendApproved {
+                id,
+                who: who.clone(),
+            });
+
+            if proposal.approvals.len() + 1 < T::SpendThreshold::get() as usize {
+                proposal
+                    .approvals
+                    .try_push(who)
+                    .expect("fewer approvals than the threshold, which bounds them; qed");
+                SpendProposals::<T>::insert(id, proposal);
+                return Ok(());
+            }
+
+            let SpendProposal {
+                proposer,
+                beneficiary,
+                amount,
+                bond,
+                ..
+            } = proposal;
+            Self::mutate_balances(Some(&Self::treasury_account()), Some(&beneficiary), amount)?;
+            if !bond.is_zero() {
+                Self::mutate_balances(Some(&Self::bond_account()), Some(&proposer), bond)?;
+            }
+            SpendProposals::<T>::remove(id);
+            Self::deposit_event(Event::<T>::SpendPaid {
+                id,
+                beneficiary,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Execute the standing order `id`, due at block `now`, and queue its next execution.
+        fn execute_scheduled(id: u32, now: BlockNumberFor<T>) {
+            let Some(mut order) = ScheduledTransfers::<T>::get(id) else {
+                return;
+            };
+
+            let (from, to, amount) = (order.from.clone(), order.dest.clone(), order.amount);
+            match Self::transfer_with_fee(&from, &to, amount) {
+                Ok(_) => Self::deposit_event(Event::<T>::ScheduledTransferExecuted {
+                    id,
+                    from,
+                    to,
+                    amount,
+                }),
+                Err(error) => Self::deposit_event(Event::<T>::ScheduledTransferFailed {
+                    id,
+                    from,
+                    to,
+                    amount,
+                    error,
+                }),
+            }
+
+            order.remaining -= 1;
+            if order.remaining == 0 {
+                Self::remove_scheduled(id, &order.from);
+                return;
+            }
+
+            order.next = now.saturating_add(order.period);
+            if Agenda::<T>::try_append(order.next, id).is_ok() {
+                ScheduledTransfers::<T>::insert(id, order);
+            } else {
+                Self::remove_scheduled(id, &order.from);
+                Self::deposit_event(Event::<T>::ScheduledTransferCancelled { id });
+            }
+        }
+
+        /// Remove the standing order `id` of `from`, freeing up one of its open orders.
+        fn remove_scheduled(id: u32, from: &T::AccountId) {
+            ScheduledTransfers::<T>::remove(id);
+            ScheduleCount::<T>::mutate_exists(from, |open| {
+                *open = open.and_then(|n| n.checked_sub(1)).filter(|n| *n > 0);
+            });
+        }
+
+        /// The part of `who`'s balance that is not locked by vesting.
+        fn free_balance(who: &T::AccountId) -> Balance {
+            Balances::<T>::get(who)
+                .unwrap_or(0)
+                .saturating_sub(Locks::<T>::get(who).unwrap_or(0))
+        }
+
+        /// Put `who`'s funds under `schedule`. The schedule holds a consumer reference, so that
+        /// the account cannot be reaped while it vests.
+        fn add_vesting_schedule(
+            who: &T::AccountId,
+            schedule: VestingInfo<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            frame_system::Pallet::<T>::inc_consumers(who)?;
+            Vesting::<T>::insert(who, schedule);
+            Self::update_lock(who);
+            Ok(())
+        }
+
+        /// Re-compute `who`'s lock from its vesting schedule, removing both once fully vested.
+        fn update_lock(who: &T::AccountId) {
+            let Some(schedule) = Vesting::<T>::get(who) else {
+                return;
+            };
+
+            let locked = schedule.locked_at(frame_system::Pallet::<T>::block_number());
+            if locked.is_zero() {
+                Vesting::<T>::remove(who);
+                Locks::<T>::remove(who);
+                frame_system::Pallet::<T>::dec_consumers(who);
+            } else {
+                Locks::<T>::insert(who, locked);
+            }
+        }
 
-            // update sender and dest balances.
-            Balances::<T>::mutate(dest, |b| *b = Some(b.unwrap_or(0) + amount));
-            Balances::<T>::insert(&sender, reminder);
+        /// Check the invariants that `mutate_balances` and the vesting and scheduling calls keep.
+        #[cfg(any(test, feature = "try-runtime"))]
+        pub fn do_try_state() -> Result<(), TryRuntimeError> {
+            // funds are only minted and burnt along with the total issuance.
+            let mut total: Balance = 0;
+            for balance in Balances::<T>::iter_values() {
+                ensure!(!balance.is_zero(), "empty balance entry");
+                total = total.checked_add(balance).ok_or("balances overflow")?;
+            }
+            ensure!(
+                total == TotalIssuance::<T>::get().unwrap_or(0),
+                "balances do not add up to the total issuance"
+            );
+
+            // a lock exists exactly while its account vests, and never exceeds its balance.
+            ensure!(
+                Vesting::<T>::iter_keys().all(Locks::<T>::contains_key),
+                "vesting schedule without a lock"
+            );
+            for (who, locked) in Locks::<T>::iter() {
+                ensure!(Vesting::<T>::contains_key(&who), "lock without a vesting schedule");
+                ensure!(
+                    Balances::<T>::get(&who).unwrap_or(0) >= locked,
+                    "lock exceeds the balance"
+                );
+            }
+
+            // every queued standing order is due at the block it is queued in.
+            let next_id = NextScheduleId::<T>::get();
+            for (n, agenda) in Agenda::<T>::iter() {
+                for id in agenda {
+                    let order = ScheduledTransfers::<T>::get(id).ok_or("unknown scheduled id")?;
+                    ensure!(id < next_id && order.next == n, "misplaced scheduled id");
+                }
+            }
+
+            // and every open order is counted against its creator.
+            let mut open = alloc::collections::BTreeMap::<T::AccountId, u32>::new();
+            for order in ScheduledTransfers::<T>::iter_values() {
+                *open.entry(order.from).or_default() += 1;
+            }
+            ensure!(
+                ScheduleCount::<T>::iter().all(|(who, n)| open.remove(&who) == Some(n))
+                    && open.is_empty(),
+                "open standing orders miscounted"
+            );
+
+            ensure!(
+                SpendProposals::<T>::iter_keys().all(|id| id < NextProposalId::<T>::get()),
+                "spend proposal id ahead of the next one"
+            );
+            ensure!(
+                SpendProposals::<T>::count() <= T::MaxSpendProposals::get(),
+                "too many spend proposals"
+            );
+            let bonds = SpendProposals::<T>::iter_values()
+                .map(|p| p.bond)
+                .sum::<Balance>();
+            ensure!(
+                Balances::<T>::get(Self::bond_account()).unwrap_or(0) == bonds,
+                "bond account does not hold exactly the open bonds"
+            );
 
             Ok(())
         }
@@ -115,13 +896,15 @@ pub mod pallet {
 
     #[cfg(any(test, doc))]
     pub(crate) mod tests {
-        use crate::guides::your_first_pallet::pallet::*;
+        use crate::{pallet::*, vesting::VestingInfo};
 
         use frame::testing_prelude::*;
 
         pub(crate) const ALICE: u64 = 1;
         pub(crate) const BOB: u64 = 2;
         pub(crate) const CHARLIE: u64 = 3;
+        pub(crate) const DAVE: u64 = 4;
+        pub(crate) const EXISTENTIAL_DEPOSIT: Balance = 10;
 
         // This runtime is only used for testing, so it should be somewhere like `#[cfg(test)] mod
         // tests { .. }`
@@ -130,7 +913,7 @@ pub mod pallet {
             // we need to reference our `mod pallet` as an identifier to pass to
             // `construct_runtime`.
             // YOU HAVE TO CHANGE THIS LINE BASED ON YOUR TEMPLATE
-            use crate::guides::your_first_pallet::pallet as pallet_currency;
+            use crate::pallet as pallet_currency;
 
             construct_runtime!(
                 pub enum Runtime {
@@ -148,8 +931,34 @@ pub mod pallet {
                 type AccountId = u64;
             }
 
-            // our simple pallet has nothing to be configured.
-            impl pallet_currency::Config for Runtime {}
+            parameter_types! {
+                pub const CurrencyPalletId: PalletId = PalletId(*b"py/trsry");
+                pub static TransferFee: Permill = Permill::zero();
+            }
+
+            pub struct Council;
+            impl SortedMembers<u64> for Council {
+                fn sorted_members() -> Vec<u64> {
+                    vec![ALICE, BOB, CHARLIE]
+                }
+            }
+
+            impl pallet_currency::Config for Runtime {
+                type RuntimeEvent = RuntimeEvent;
+                type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+                type MaxScheduledPerBlock = ConstU32<2>;
+                type MaxScheduledPerAccount = ConstU32<3>;
+                type PalletId = CurrencyPalletId;
+                type TransferFee = TransferFee;
+                type Council = Council;
+                type SpendThreshold = ConstU32<2>;
+                type SpendProposalBond = ConstU128<EXISTENTIAL_DEPOSIT>;
+                type MaxSpendProposals = ConstU32<2>;
+                type RejectOrigin = EnsureRoot<u64>;
+                type MaxCheckpoints = ConstU32<3>;
+                type MintOrigin = EnsureSigned<u64>;
+                type WeightInfo = ();
+            }
         }
 
         pub(crate) use runtime::*;
@@ -170,12 +979,14 @@ pub mod pallet {
 
         pub(crate) struct StateBuilder {
             balances: Vec<(<Runtime as frame_system::Config>::AccountId, Balance)>,
+            try_state: bool,
         }
 
         impl Default for StateBuilder {
             fn default() -> Self {
                 Self {
                     balances: vec![(ALICE, 100), (BOB, 100)],
+                    try_state: true,
                 }
             }
         }
@@ -189,6 +1000,12 @@ pub mod pallet {
                 self.balances.push((who, amount));
                 self
             }
+
+            /// Skip the `try_state` checks of this pallet after the test.
+            pub(crate) fn without_try_state(mut self) -> Self {
+                self.try_state = false;
+                self
+            }
         }
 
         impl StateBuilder {
@@ -198,6 +1015,7 @@ pub mod pallet {
                     for (who, amount) in &self.balances {
                         Balances::<Runtime>::insert(who, amount);
                         TotalIssuance::<Runtime>::mutate(|b| *b = Some(b.unwrap_or(0) + amount));
+                        System::inc_providers(who);
                     }
                 });
 
@@ -209,6 +1027,9 @@ pub mod pallet {
                         Balances::<Runtime>::iter().map(|(_, x)| x).sum::<u128>(),
                         TotalIssuance::<Runtime>::get().unwrap_or_default()
                     );
+                    if self.try_state {
+                        assert_ok!(Pallet::<Runtime>::do_try_state());
+                    }
                 })
             }
         }
@@ -337,23 +1158,1005 @@ pub mod pallet {
                 assert_eq!(TotalIssuance::<Runtime>::get(), Some(200));
             });
         }
-    }
-}
 
-#[frame::pallet(dev_mode)]
-pub mod pallet_v2 {
-    use super::pallet::Balance;
-    use frame::prelude::*;
+        #[test]
+        fn vested_transfer_locks_funds() {
+            StateBuilder::default().build_and_execute(|| {
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::vested_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    VestingInfo::new(50, 10, 10)
+                ));
 
-    #[pallet::config]
-    pub trait Config: frame_system::Config {
-        /// The overarching event type of the runtime.
-        type RuntimeEvent: From<Event<Self>>
-            + IsType<<Self as frame_system::Config>::RuntimeEvent>
-            + TryInto<Event<Self>>;
-    }
+                // then:
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(50));
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(150));
+                assert_eq!(Locks::<Runtime>::get(BOB), Some(50));
 
-    #[pallet::pallet]
+                // the locked part cannot be moved, the rest can.
+                assert_err!(
+                    Pallet::<Runtime>::transfer(RuntimeOrigin::signed(BOB), ALICE, 101),
+                    "LiquidityRestrictions"
+                );
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(BOB),
+                    ALICE,
+                    100
+                ));
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(50));
+            });
+        }
+
+        #[test]
+        fn vested_transfer_rejects_bad_schedules() {
+            StateBuilder::default().build_and_execute(|| {
+                assert_err!(
+                    Pallet::<Runtime>::vested_transfer(
+                        RuntimeOrigin::signed(ALICE),
+                        BOB,
+                        VestingInfo::new(50, 0, 10)
+                    ),
+                    "InvalidScheduleParams"
+                );
+
+                assert_ok!(Pallet::<Runtime>::vested_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    VestingInfo::new(50, 10, 10)
+                ));
+                assert_err!(
+                    Pallet::<Runtime>::vested_transfer(
+                        RuntimeOrigin::signed(ALICE),
+                        BOB,
+                        VestingInfo::new(10, 1, 10)
+                    ),
+                    "ExistingVestingSchedule"
+                );
+            });
+        }
+
+        #[test]
+        fn vest_unlocks_matured_portion() {
+            StateBuilder::default().build_and_execute(|| {
+                assert_ok!(Pallet::<Runtime>::vested_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    VestingInfo::new(50, 10, 10)
+                ));
+
+                // nothing has matured yet.
+                System::set_block_number(10);
+                assert_ok!(Pallet::<Runtime>::vest(RuntimeOrigin::signed(BOB)));
+                assert_eq!(Locks::<Runtime>::get(BOB), Some(50));
+
+                // three blocks worth of funds have matured.
+                System::set_block_number(13);
+                assert_ok!(Pallet::<Runtime>::vest(RuntimeOrigin::signed(BOB)));
+                assert_eq!(Locks::<Runtime>::get(BOB), Some(20));
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(BOB),
+                    ALICE,
+                    130
+                ));
+
+                // fully vested, the schedule is cleaned up.
+                System::set_block_number(15);
+                assert_ok!(Pallet::<Runtime>::vest(RuntimeOrigin::signed(BOB)));
+                assert_eq!(Locks::<Runtime>::get(BOB), None);
+                assert_eq!(Vesting::<Runtime>::get(BOB), None);
+                assert_err!(
+                    Pallet::<Runtime>::vest(RuntimeOrigin::signed(BOB)),
+                    "NotVesting"
+                );
+            });
+        }
+
+        #[test]
+        fn genesis_vesting_works() {
+            let mut ext: TestState = RuntimeGenesisConfig {
+                currency: GenesisConfig {
+                    balances: vec![(ALICE, 100), (BOB, 100)],
+                    vesting: vec![(ALICE, 0, 10, 20)],
+                },
+                ..Default::default()
+            }
+            .build_storage()
+            .unwrap()
+            .into();
+
+            ext.execute_with(|| {
+                assert_eq!(TotalIssuance::<Runtime>::get(), Some(200));
+                assert_eq!(
+                    Vesting::<Runtime>::get(ALICE),
+                    Some(VestingInfo::new(80, 8, 0))
+                );
+                assert_eq!(Locks::<Runtime>::get(ALICE), Some(80));
+                assert_err!(
+                    Pallet::<Runtime>::transfer(RuntimeOrigin::signed(ALICE), BOB, 21),
+                    "LiquidityRestrictions"
+                );
+
+                System::set_block_number(5);
+                assert_ok!(Pallet::<Runtime>::vest(RuntimeOrigin::signed(ALICE)));
+                assert_eq!(Locks::<Runtime>::get(ALICE), Some(40));
+            });
+        }
+
+        #[test]
+        fn transfer_to_self_keeps_balance() {
+            StateBuilder::default().build_and_execute(|| {
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    ALICE,
+                    50
+                ));
+
+                // then nothing has changed.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(100));
+                assert_eq!(TotalIssuance::<Runtime>::get(), Some(200));
+
+                // and it is still checked against the balance.
+                assert_err!(
+                    Pallet::<Runtime>::transfer(RuntimeOrigin::signed(ALICE), ALICE, 101),
+                    "InsufficientBalance"
+                );
+            });
+        }
+
+        #[test]
+        fn new_accounts_require_existential_deposit() {
+            StateBuilder::default().build_and_execute(|| {
+                // given the initial state, when:
+                assert_err!(
+                    Pallet::<Runtime>::transfer(
+                        RuntimeOrigin::signed(ALICE),
+                        CHARLIE,
+                        EXISTENTIAL_DEPOSIT - 1
+                    ),
+                    "ExistentialDeposit"
+                );
+                assert_err!(
+                    Pallet::<Runtime>::mint_unsafe(
+                        RuntimeOrigin::signed(ALICE),
+                        CHARLIE,
+                        EXISTENTIAL_DEPOSIT - 1
+                    ),
+                    "ExistentialDeposit"
+                );
+
+                // then no account has been created.
+                assert_eq!(Balances::<Runtime>::get(CHARLIE), None);
+                assert_eq!(System::providers(&CHARLIE), 0);
+
+                // when:
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    CHARLIE,
+                    EXISTENTIAL_DEPOSIT
+                ));
+
+                // then:
+                assert_eq!(Balances::<Runtime>::get(CHARLIE), Some(EXISTENTIAL_DEPOSIT));
+                assert_eq!(System::providers(&CHARLIE), 1);
+
+                // existing accounts can receive any amount.
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    CHARLIE,
+                    1
+                ));
+            });
+        }
+
+        #[test]
+        fn emptied_accounts_are_removed() {
+            StateBuilder::default().build_and_execute(|| {
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    100
+                ));
+
+                // then:
+                assert_eq!(Balances::<Runtime>::get(ALICE), None);
+                assert_eq!(System::providers(&ALICE), 0);
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(200));
+                assert_eq!(System::providers(&BOB), 1);
+            });
+        }
+
+        #[test]
+        fn vesting_holds_a_consumer_reference() {
+            StateBuilder::default().build_and_execute(|| {
+                assert_ok!(Pallet::<Runtime>::vested_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    CHARLIE,
+                    VestingInfo::new(50, 10, 0)
+                ));
+                assert_eq!(System::providers(&CHARLIE), 1);
+                assert_eq!(System::consumers(&CHARLIE), 1);
+
+                System::set_block_number(5);
+                assert_ok!(Pallet::<Runtime>::vest(RuntimeOrigin::signed(CHARLIE)));
+                assert_eq!(System::consumers(&CHARLIE), 0);
+
+                // once unlocked, the account can be emptied and reaped.
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(CHARLIE),
+                    ALICE,
+                    50
+                ));
+                assert_eq!(System::providers(&CHARLIE), 0);
+            });
+        }
+
+        fn run_to_block(n: u64) {
+            while System::block_number() < n {
+                let next = System::block_number() + 1;
+                System::set_block_number(next);
+                Pallet::<Runtime>::on_initialize(next);
+            }
+        }
+
+        #[test]
+        fn scheduled_transfer_executes_every_period() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(1);
+
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::schedule_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    10,
+                    2,
+                    3,
+                    3
+                ));
+
+                // then nothing has been paid yet.
+                assert_eq!(Agenda::<Runtime>::get(2).into_inner(), vec![0]);
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(100));
+
+                // when:
+                run_to_block(7);
+
+                // then it has paid out at blocks 2 and 5.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(80));
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(120));
+                assert_eq!(ScheduledTransfers::<Runtime>::get(0).unwrap().next, 8);
+
+                // when:
+                run_to_block(20);
+
+                // then it has paid out for the last time at block 8.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(70));
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(130));
+                assert_eq!(ScheduledTransfers::<Runtime>::get(0), None);
+
+                let executed = Event::ScheduledTransferExecuted {
+                    id: 0,
+                    from: ALICE,
+                    to: BOB,
+                    amount: 10,
+                };
+                assert_eq!(
+                    System::read_events_for_pallet::<Event<Runtime>>(),
+                    vec![
+                        Event::TransferScheduled {
+                            id: 0,
+                            from: ALICE,
+                            to: BOB,
+                            amount: 10,
+                            start: 2
+                        },
+                        executed.clone(),
+                        executed.clone(),
+                        executed,
+                    ]
+                );
+            });
+        }
+
+        #[test]
+        fn scheduled_transfer_reports_insufficient_funds() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(1);
+
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::schedule_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    60,
+                    2,
+                    1,
+                    2
+                ));
+                run_to_block(3);
+
+                // then only the first payment went through. The `InsufficientBalance` message is
+                // not stored along with the event.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(40));
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(160));
+                assert_eq!(
+                    System::read_events_for_pallet::<Event<Runtime>>().last(),
+                    Some(&Event::ScheduledTransferFailed {
+                        id: 0,
+                        from: ALICE,
+                        to: BOB,
+                        amount: 60,
+                        error: DispatchError::Other(""),
+                    })
+                );
+                assert_eq!(ScheduledTransfers::<Runtime>::get(0), None);
+            });
+        }
+
+        #[test]
+        fn cancel_scheduled_transfer_works() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(1);
+                assert_ok!(Pallet::<Runtime>::schedule_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    10,
+                    2,
+                    1,
+                    5
+                ));
+
+                // only the creator can cancel.
+                assert_err!(
+                    Pallet::<Runtime>::cancel_scheduled_transfer(RuntimeOrigin::signed(BOB), 0),
+                    "NotScheduleOwner"
+                );
+                assert_ok!(Pallet::<Runtime>::cancel_scheduled_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    0
+                ));
+                assert_err!(
+                    Pallet::<Runtime>::cancel_scheduled_transfer(RuntimeOrigin::signed(ALICE), 0),
+                    "UnknownSchedule"
+                );
+
+                // then nothing is paid out.
+                assert!(Agenda::<Runtime>::get(2).is_empty());
+                run_to_block(10);
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(100));
+            });
+        }
+
+        #[test]
+        fn schedule_transfer_checks_parameters() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(5);
+                let schedule = |start, period, count| {
+                    Pallet::<Runtime>::schedule_transfer(
+                        RuntimeOrigin::signed(ALICE),
+                        BOB,
+                        10,
+                        start,
+                        period,
+                        count,
+                    )
+                };
+
+                assert_err!(schedule(5, 1, 1), "ScheduleInPast");
+                assert_err!(schedule(6, 1, 0), "InvalidSchedule");
+                assert_err!(schedule(6, 0, 2), "InvalidSchedule");
+
+                // at most two orders per block in the mock runtime.
+                assert_ok!(schedule(6, 0, 1));
+                assert_ok!(schedule(6, 0, 1));
+                assert_err!(schedule(6, 0, 1), "AgendaFull");
+            });
+        }
+
+        #[test]
+        fn schedule_transfer_is_limited_by_funds_and_open_orders() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(1);
+                let schedule = |amount, start| {
+                    Pallet::<Runtime>::schedule_transfer(
+                        RuntimeOrigin::signed(ALICE),
+                        BOB,
+                        amount,
+                        start,
+                        1,
+                        1,
+                    )
+                };
+
+                // the first payment must be affordable when the order is made.
+                assert_err!(schedule(101, 2), "InsufficientBalance");
+
+                // at most three open orders per account in the mock runtime.
+                assert_ok!(schedule(10, 2));
+                assert_ok!(schedule(10, 3));
+                assert_ok!(schedule(10, 4));
+                assert_err!(schedule(10, 5), "TooManySchedules");
+                assert_eq!(ScheduleCount::<Runtime>::get(ALICE), 3);
+
+                // cancelled and completed orders free up their slot.
+                assert_ok!(Pallet::<Runtime>::cancel_scheduled_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    2
+                ));
+                run_to_block(2);
+                assert_eq!(ScheduleCount::<Runtime>::get(ALICE), 1);
+                assert_ok!(schedule(10, 5));
+                assert_ok!(schedule(10, 6));
+                assert_err!(schedule(10, 7), "TooManySchedules");
+            });
+        }
+
+        #[test]
+        fn transfer_pays_fee_to_treasury() {
+            StateBuilder::default().build_and_execute(|| {
+                TransferFee::set(Permill::from_percent(10));
+                let treasury = Pallet::<Runtime>::treasury_account();
+
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    50
+                ));
+
+                // then 10% of it went to the treasury, even though it is below the existential
+                // deposit.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(50));
+                assert_eq!(Balances::<Runtime>::get(BOB), Some(145));
+                assert_eq!(Balances::<Runtime>::get(treasury), Some(5));
+                assert_eq!(TotalIssuance::<Runtime>::get(), Some(200));
+            });
+        }
+
+        #[test]
+        fn vested_transfer_pays_fee_to_treasury() {
+            StateBuilder::default().build_and_execute(|| {
+                TransferFee::set(Permill::from_percent(10));
+                let treasury = Pallet::<Runtime>::treasury_account();
+
+                // given the initial state, when a schedule that unlocks within a block is transferred:
+                assert_ok!(Pallet::<Runtime>::vested_transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    CHARLIE,
+                    VestingInfo::new(50, 50, 0)
+                ));
+
+                // then it paid the same fee as a plain transfer, and only what arrived is locked.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(50));
+                assert_eq!(Balances::<Runtime>::get(CHARLIE), Some(45));
+                assert_eq!(Balances::<Runtime>::get(treasury), Some(5));
+                assert_eq!(
+                    Vesting::<Runtime>::get(CHARLIE),
+                    Some(VestingInfo::new(45, 50, 0))
+                );
+            });
+        }
+
+        #[test]
+        fn spend_is_paid_once_approved_by_threshold() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(1);
+                let treasury = Pallet::<Runtime>::treasury_account();
+                assert_ok!(Pallet::<Runtime>::mint_unsafe(
+                    RuntimeOrigin::signed(ALICE),
+                    treasury,
+                    30
+                ));
+
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::propose_spend(
+                    RuntimeOrigin::signed(ALICE),
+                    DAVE,
+                    20
+                ));
+
+                // then one approval is not enough.
+                assert_eq!(Balances::<Runtime>::get(DAVE), None);
+                assert_eq!(
+                    SpendProposals::<Runtime>::get(0).unwrap().approvals,
+                    vec![ALICE]
+                );
+
+                // only other council members can add theirs.
+                assert_err!(
+                    Pallet::<Runtime>::approve_spend(RuntimeOrigin::signed(ALICE), 0),
+                    "AlreadyApproved"
+                );
+                assert_err!(
+                    Pallet::<Runtime>::approve_spend(RuntimeOrigin::signed(DAVE), 0),
+                    "NotCouncilMember"
+                );
+
+                // when:
+                assert_ok!(Pallet::<Runtime>::approve_spend(
+                    RuntimeOrigin::signed(BOB),
+                    0
+                ));
+
+                // then:
+                assert_eq!(Balances::<Runtime>::get(DAVE), Some(20));
+                assert_eq!(Balances::<Runtime>::get(treasury), Some(10));
+                assert_eq!(SpendProposals::<Runtime>::get(0), None);
+                assert_eq!(
+                    System::read_events_for_pallet::<Event<Runtime>>(),
+                    vec![
+                        Event::SpendProposed {
+                            id: 0,
+                            proposer: ALICE,
+                            beneficiary: DAVE,
+                            amount: 20
+                        },
+                        Event::SpendApproved { id: 0, who: ALICE },
+                        Event::SpendApproved { id: 0, who: BOB },
+                        Event::SpendPaid {
+                            id: 0,
+                            beneficiary: DAVE,
+                            amount: 20
+                        },
+                    ]
+                );
+            });
+        }
+
+        #[test]
+        fn spend_checks_council_and_treasury_funds() {
+            StateBuilder::default().build_and_execute(|| {
+                assert_err!(
+                    Pallet::<Runtime>::propose_spend(RuntimeOrigin::signed(DAVE), DAVE, 20),
+                    "NotCouncilMember"
+                );
+                assert_err!(
+                    Pallet::<Runtime>::approve_spend(RuntimeOrigin::signed(ALICE), 0),
+                    "UnknownProposal"
+                );
+
+                // the treasury is empty, so the final approval fails and can be retried later.
+                assert_ok!(Pallet::<Runtime>::propose_spend(
+                    RuntimeOrigin::signed(ALICE),
+                    DAVE,
+                    20
+                ));
+                assert_err!(
+                    Pallet::<Runtime>::approve_spend(RuntimeOrigin::signed(BOB), 0),
+                    "NonExistentAccount"
+                );
+                assert_eq!(
+                    SpendProposals::<Runtime>::get(0).unwrap().approvals,
+                    vec![ALICE]
+                );
+            });
+        }
+
+        #[test]
+        fn spend_bond_is_returned_once_paid_out() {
+            StateBuilder::default().build_and_execute(|| {
+                let treasury = Pallet::<Runtime>::treasury_account();
+                let bond_account = Pallet::<Runtime>::bond_account();
+                assert_ok!(Pallet::<Runtime>::mint_unsafe(
+                    RuntimeOrigin::signed(ALICE),
+                    treasury,
+                    30
+                ));
+
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::propose_spend(
+                    RuntimeOrigin::signed(ALICE),
+                    DAVE,
+                    20
+                ));
+
+                // then the bond is held until the spend is paid out.
+                assert_eq!(
+                    Balances::<Runtime>::get(ALICE),
+                    Some(100 - EXISTENTIAL_DEPOSIT)
+                );
+                assert_eq!(
+                    Balances::<Runtime>::get(bond_account),
+                    Some(EXISTENTIAL_DEPOSIT)
+                );
+                assert_eq!(
+                    SpendProposals::<Runtime>::get(0).unwrap().bond,
+                    EXISTENTIAL_DEPOSIT
+                );
+
+                // when:
+                assert_ok!(Pallet::<Runtime>::approve_spend(
+                    RuntimeOrigin::signed(BOB),
+                    0
+                ));
+
+                // then:
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(100));
+                assert_eq!(Balances::<Runtime>::get(bond_account), None);
+                assert_eq!(Balances::<Runtime>::get(DAVE), Some(20));
+
+                // a council member without the bond can't propose.
+                assert_err!(
+                    Pallet::<Runtime>::propose_spend(RuntimeOrigin::signed(CHARLIE), DAVE, 20),
+                    "NonExistentAccount"
+                );
+            });
+        }
+
+        #[test]
+        fn rejected_spend_slashes_the_bond() {
+            StateBuilder::default().build_and_execute(|| {
+                System::set_block_number(1);
+                let treasury = Pallet::<Runtime>::treasury_account();
+                assert_ok!(Pallet::<Runtime>::propose_spend(
+                    RuntimeOrigin::signed(ALICE),
+                    DAVE,
+                    20
+                ));
+
+                // only the reject origin can reject.
+                assert_err!(
+                    Pallet::<Runtime>::reject_spend(RuntimeOrigin::signed(BOB), 0),
+                    DispatchError::BadOrigin
+                );
+                assert_err!(
+                    Pallet::<Runtime>::reject_spend(RuntimeOrigin::root(), 1),
+                    "UnknownProposal"
+                );
+
+                // when:
+                assert_ok!(Pallet::<Runtime>::reject_spend(RuntimeOrigin::root(), 0));
+
+                // then the bond goes to the treasury, and the proposal can't be approved anymore.
+                assert_eq!(SpendProposals::<Runtime>::get(0), None);
+                assert_eq!(
+                    Balances::<Runtime>::get(ALICE),
+                    Some(100 - EXISTENTIAL_DEPOSIT)
+                );
+                assert_eq!(
+                    Balances::<Runtime>::get(treasury),
+                    Some(EXISTENTIAL_DEPOSIT)
+                );
+                assert_eq!(
+                    Balances::<Runtime>::get(Pallet::<Runtime>::bond_account()),
+                    None
+                );
+                assert_err!(
+                    Pallet::<Runtime>::approve_spend(RuntimeOrigin::signed(BOB), 0),
+                    "UnknownProposal"
+                );
+                System::assert_last_event(
+                    Event::SpendRejected {
+                        id: 0,
+                        slashed: EXISTENTIAL_DEPOSIT,
+                    }
+                    .into(),
+                );
+            });
+        }
+
+        #[test]
+        fn spend_proposals_are_bounded() {
+            StateBuilder::default().build_and_execute(|| {
+                // given the initial state, when:
+                for id in 0..2 {
+                    assert_ok!(Pallet::<Runtime>::propose_spend(
+                        RuntimeOrigin::signed(ALICE),
+                        DAVE,
+                        20
+                    ));
+                    assert!(SpendProposals::<Runtime>::contains_key(id));
+                }
+
+                // then:
+                assert_err!(
+                    Pallet::<Runtime>::propose_spend(RuntimeOrigin::signed(BOB), DAVE, 20),
+                    "TooManyProposals"
+                );
+
+                // rejecting one makes room again.
+                assert_ok!(Pallet::<Runtime>::reject_spend(RuntimeOrigin::root(), 0));
+                assert_ok!(Pallet::<Runtime>::propose_spend(
+                    RuntimeOrigin::signed(BOB),
+                    DAVE,
+                    20
+                ));
+                assert_eq!(SpendProposals::<Runtime>::count(), 2);
+            });
+        }
+
+        #[test]
+        fn balance_history_is_checkpointed() {
+            StateBuilder::default().build_and_execute(|| {
+                // given the initial state, when:
+                for n in 1..=4 {
+                    System::set_block_number(n);
+                    assert_ok!(Pallet::<Runtime>::mint_unsafe(
+                        RuntimeOrigin::signed(ALICE),
+                        CHARLIE,
+                        10
+                    ));
+                    assert_ok!(Pallet::<Runtime>::mint_unsafe(
+                        RuntimeOrigin::signed(ALICE),
+                        CHARLIE,
+                        10
+                    ));
+                }
+                System::set_block_number(6);
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(CHARLIE),
+                    DAVE,
+                    30
+                ));
+
+                // then only the last 3 changes of every history are kept, one per block.
+                assert_eq!(
+                    BalanceHistory::<Runtime>::get(CHARLIE).history.into_inner(),
+                    vec![(3, 60), (4, 80), (6, 50)]
+                );
+                assert_eq!(Pallet::<Runtime>::balance_at(&CHARLIE, 2), None);
+                assert_eq!(Pallet::<Runtime>::balance_at(&CHARLIE, 3), Some(60));
+                assert_eq!(Pallet::<Runtime>::balance_at(&CHARLIE, 5), Some(80));
+                assert_eq!(Pallet::<Runtime>::balance_at(&CHARLIE, 6), Some(50));
+                assert_eq!(Pallet::<Runtime>::balance_at(&CHARLIE, 7), None);
+
+                // DAVE's history starts with the transfer, so it had nothing before.
+                assert_eq!(Pallet::<Runtime>::balance_at(&DAVE, 5), Some(0));
+                assert_eq!(Pallet::<Runtime>::balance_at(&DAVE, 6), Some(30));
+
+                // the transfer does not change the issuance.
+                assert_eq!(Pallet::<Runtime>::total_issuance_at(1), None);
+                assert_eq!(Pallet::<Runtime>::total_issuance_at(2), Some(240));
+                assert_eq!(Pallet::<Runtime>::total_issuance_at(6), Some(280));
+            });
+        }
+
+        #[test]
+        fn reaped_accounts_leave_no_storage() {
+            use frame::deps::{frame_support::StorageHasher, sp_io::storage};
+
+            StateBuilder::default().build_and_execute(|| {
+                // every map keyed by accounts hashes them with `Blake2_128Concat`.
+                let key = Blake2_128Concat::hash(&CHARLIE.encode());
+                let has_storage = || {
+                    let mut prev = Vec::new();
+                    while let Some(next) = storage::next_key(&prev) {
+                        if next.ends_with(&key) {
+                            return true;
+                        }
+                        prev = next;
+                    }
+                    false
+                };
+
+                // given an account with a history over several blocks.
+                System::set_block_number(1);
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    CHARLIE,
+                    50
+                ));
+                System::set_block_number(2);
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(CHARLIE),
+                    BOB,
+                    20
+                ));
+                assert!(has_storage());
+
+                // when it is emptied:
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(CHARLIE),
+                    BOB,
+                    30
+                ));
+
+                // then nothing is left of it, and its past balances read as zero.
+                assert!(!has_storage());
+                assert_eq!(Pallet::<Runtime>::balance_at(&CHARLIE, 1), Some(0));
+            });
+        }
+
+        #[test]
+        fn mint_overflow_is_rolled_back() {
+            StateBuilder::default().build_and_execute(|| {
+                // `CHARLIE`'s balance would fit, but the total issuance would not.
+                assert_err!(
+                    Pallet::<Runtime>::mint_unsafe(
+                        RuntimeOrigin::signed(ALICE),
+                        CHARLIE,
+                        Balance::MAX
+                    ),
+                    ArithmeticError::Overflow
+                );
+
+                // then nothing has changed.
+                assert_eq!(Balances::<Runtime>::get(CHARLIE), None);
+                assert_eq!(TotalIssuance::<Runtime>::get(), Some(200));
+            });
+        }
+
+        /// Randomized tests over sequences of calls, checked against a trivially correct model
+        /// after every step. Any panic inside the pallet fails the property as well.
+        pub(crate) mod fuzz {
+            use super::*;
+            use proptest::prelude::*;
+            use std::collections::BTreeMap;
+
+            /// Accounts that calls are drawn from. Only `ALICE` and `BOB` start with a balance.
+            pub(crate) const ACCOUNTS: core::ops::RangeInclusive<u64> = 1..=16;
+
+            /// A call that can be dispatched by the randomized tests.
+            #[derive(Debug, Clone)]
+            pub(crate) enum Op {
+                Mint { dest: u64, amount: Balance },
+                Transfer { from: u64, to: u64, amount: Balance },
+            }
+
+            /// Amounts biased towards the edges: zero, small values and values close to overflow.
+            fn amount() -> impl Strategy<Value = Balance> {
+                prop_oneof![
+                    4 => 0..=300u128,
+                    1 => Just(0),
+                    1 => (Balance::MAX - 300)..=Balance::MAX,
+                ]
+            }
+
+            pub(crate) fn transfer() -> impl Strategy<Value = Op> {
+                (ACCOUNTS, ACCOUNTS, amount()).prop_map(|(from, to, amount)| Op::Transfer {
+                    from,
+                    to,
+                    amount,
+                })
+            }
+
+            pub(crate) fn op() -> impl Strategy<Value = Op> {
+                prop_oneof![
+                    (ACCOUNTS, amount()).prop_map(|(dest, amount)| Op::Mint { dest, amount }),
+                    transfer(),
+                ]
+            }
+
+            /// What the currency should look like, without any of the storage details.
+            pub(crate) struct Model {
+                balances: BTreeMap<u64, Balance>,
+                issuance: Balance,
+                /// `None` if the currency keeps empty entries and lets anyone create new ones.
+                existential_deposit: Option<Balance>,
+            }
+
+            impl Model {
+                /// Snapshot of `balances` and `issuance` as they are before any call.
+                pub(crate) fn new(
+                    balances: impl Iterator<Item = (u64, Balance)>,
+                    issuance: Option<Balance>,
+                    existential_deposit: Option<Balance>,
+                ) -> Self {
+                    Self {
+                        balances: balances.collect(),
+                        issuance: issuance.unwrap_or_default(),
+                        existential_deposit,
+                    }
+                }
+
+                fn can_create(&self, who: u64, amount: Balance) -> bool {
+                    self.balances.contains_key(&who)
+                        || self.existential_deposit.is_none_or(|ed| amount >= ed)
+                }
+
+                /// Apply `op` to the model, returning whether the pallet should accept it.
+                pub(crate) fn apply(&mut self, op: &Op) -> bool {
+                    match *op {
+                        Op::Mint { dest, amount } => {
+                            let Some(issuance) = self.issuance.checked_add(amount) else {
+                                return false;
+                            };
+                            if !self.can_create(dest, amount) {
+                                return false;
+                            }
+                            self.issuance = issuance;
+                            // cannot overflow, as no balance exceeds the issuance.
+                            *self.balances.entry(dest).or_default() += amount;
+                        }
+                        Op::Transfer { from, to, amount } => {
+                            let Some(reminder) = self
+                                .balances
+                                .get(&from)
+                                .and_then(|balance| balance.checked_sub(amount))
+                            else {
+                                return false;
+                            };
+                            if from == to {
+                                return true;
+                            }
+                            if !self.can_create(to, amount) {
+                                return false;
+                            }
+
+                            if reminder == 0 && self.existential_deposit.is_some() {
+                                self.balances.remove(&from);
+                            } else {
+                                self.balances.insert(from, reminder);
+                            }
+                            *self.balances.entry(to).or_default() += amount;
+                        }
+                    }
+                    true
+                }
+
+                /// Assert that the on-chain state matches the model, and is thus conserved.
+                pub(crate) fn assert_matches(
+                    &self,
+                    balances: impl Iterator<Item = (u64, Balance)>,
+                    issuance: Option<Balance>,
+                ) {
+                    let balances = balances.collect::<BTreeMap<_, _>>();
+                    assert_eq!(balances, self.balances);
+                    assert_eq!(issuance.unwrap_or_default(), self.issuance);
+                    assert_eq!(balances.values().sum::<Balance>(), self.issuance);
+                }
+            }
+
+            fn dispatch(op: &Op) -> DispatchResult {
+                match *op {
+                    Op::Mint { dest, amount } => {
+                        Pallet::<Runtime>::mint_unsafe(RuntimeOrigin::signed(ALICE), dest, amount)
+                    }
+                    Op::Transfer { from, to, amount } => {
+                        Pallet::<Runtime>::transfer(RuntimeOrigin::signed(from), to, amount)
+                    }
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn random_calls_match_model(ops in prop::collection::vec(op(), 0..64)) {
+                    StateBuilder::default().build_and_execute(|| {
+                        let mut model = Model::new(
+                            Balances::<Runtime>::iter(),
+                            TotalIssuance::<Runtime>::get(),
+                            Some(EXISTENTIAL_DEPOSIT),
+                        );
+
+                        for op in ops {
+                            let expected = model.apply(&op);
+                            let result = dispatch(&op);
+                            assert_eq!(result.is_ok(), expected, "{op:?} returned {result:?}");
+
+                            model.assert_matches(
+                                Balances::<Runtime>::iter(),
+                                TotalIssuance::<Runtime>::get(),
+                            );
+                            // exactly the accounts with a balance are provided for.
+                            for who in ACCOUNTS {
+                                assert_eq!(
+                                    System::providers(&who),
+                                    Balances::<Runtime>::contains_key(who) as u32
+                                );
+                            }
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[frame::pallet(dev_mode)]
+pub mod pallet_v2 {
+    use super::pallet::Balance;
+    use frame::{arithmetic::ArithmeticError, prelude::*};
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type of the runtime.
+        type RuntimeEvent: From<Event<Self>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>
+            + TryInto<Event<Self>>;
+    }
+
+    #[pallet::pallet]
     pub struct Pallet<T>(_);
 
     #[pallet::storage]
@@ -390,15 +2193,7 @@ pub mod pallet_v2 {
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            // ensure sender has enough balance, and if so, calculate what is left after `amount`.
-            let sender_balance =
-                Balances::<T>::get(&sender).ok_or(Error::<T>::NonExistentAccount)?;
-            let reminder = sender_balance
-                .checked_sub(amount)
-                .ok_or(Error::<T>::InsufficientBalance)?;
-
-            Balances::<T>::mutate(&dest, |b| *b = Some(b.unwrap_or(0) + amount));
-            Balances::<T>::insert(&sender, reminder);
+            Self::mutate_balances(&sender, &dest, amount)?;
 
             Self::deposit_event(Event::<T>::Transferred {
                 from: sender,
@@ -410,6 +2205,37 @@ pub mod pallet_v2 {
         }
     }
 
+    impl<T: Config> Pallet<T> {
+        /// Move `amount` from `from` to `to`, all or nothing.
+        ///
+        /// `from` is debited before `to` is credited, and each step re-reads storage, so a
+        /// transfer to self leaves the balance untouched.
+        fn mutate_balances(
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: Balance,
+        ) -> DispatchResult {
+            storage::with_storage_layer(|| {
+                // ensure sender has enough balance, and if so, calculate what is left after
+                // `amount`.
+                let balance = Balances::<T>::get(from).ok_or(Error::<T>::NonExistentAccount)?;
+                let reminder = balance
+                    .checked_sub(amount)
+                    .ok_or(Error::<T>::InsufficientBalance)?;
+                Balances::<T>::insert(from, reminder);
+
+                Balances::<T>::try_mutate(to, |b| -> DispatchResult {
+                    *b = Some(
+                        b.unwrap_or(0)
+                            .checked_add(amount)
+                            .ok_or(ArithmeticError::Overflow)?,
+                    );
+                    Ok(())
+                })
+            })
+        }
+    }
+
     #[cfg(any(test, doc))]
     pub mod tests {
         use super::{super::pallet::tests::StateBuilder, *};
@@ -417,9 +2243,15 @@ pub mod pallet_v2 {
         const ALICE: u64 = 1;
         const BOB: u64 = 2;
 
+        /// The state of the tests of `pallet`, without its `try_state` checks, as this pallet
+        /// keeps empty balance entries.
+        fn state() -> StateBuilder {
+            StateBuilder::default().without_try_state()
+        }
+
         pub mod runtime_v2 {
             use super::*;
-            use crate::guides::your_first_pallet::pallet_v2 as pallet_currency;
+            use crate::pallet_v2 as pallet_currency;
 
             construct_runtime!(
                 pub enum Runtime {
@@ -443,7 +2275,7 @@ pub mod pallet_v2 {
 
         #[test]
         fn transfer_works() {
-            StateBuilder::default().build_and_execute(|| {
+            state().build_and_execute(|| {
                 // skip the genesis block, as events are not deposited there and we need them for
                 // the final assertion.
                 System::set_block_number(ALICE);
@@ -471,5 +2303,69 @@ pub mod pallet_v2 {
                 );
             });
         }
+
+        #[test]
+        fn transfer_to_self_keeps_balance() {
+            state().build_and_execute(|| {
+                // given the initial state, when:
+                assert_ok!(Pallet::<Runtime>::transfer(
+                    RuntimeOrigin::signed(ALICE),
+                    ALICE,
+                    50
+                ));
+
+                // then nothing has changed.
+                assert_eq!(Balances::<Runtime>::get(ALICE), Some(100));
+                assert_eq!(TotalIssuance::<Runtime>::get(), Some(200));
+            });
+        }
+
+        mod fuzz {
+            use super::*;
+            use crate::pallet::tests::fuzz::{transfer, Model, Op};
+            use proptest::prelude::*;
+
+            proptest! {
+                #[test]
+                fn random_transfers_deposit_matching_events(
+                    ops in prop::collection::vec(transfer(), 0..64)
+                ) {
+                    state().build_and_execute(|| {
+                        // events are not deposited in the genesis block.
+                        System::set_block_number(1);
+                        let mut model = Model::new(
+                            Balances::<Runtime>::iter(),
+                            TotalIssuance::<Runtime>::get(),
+                            None,
+                        );
+
+                        for op in ops {
+                            let Op::Transfer { from, to, amount } = op else {
+                                unreachable!("only transfers are generated");
+                            };
+                            System::reset_events();
+
+                            let expected = model.apply(&op);
+                            let result =
+                                Pallet::<Runtime>::transfer(RuntimeOrigin::signed(from), to, amount);
+                            assert_eq!(result.is_ok(), expected, "{op:?} returned {result:?}");
+
+                            // exactly one event per successful transfer, none otherwise.
+                            let events = System::read_events_for_pallet::<Event<Runtime>>();
+                            if expected {
+                                assert_eq!(events, vec![Event::Transferred { from, to, amount }]);
+                            } else {
+                                assert_eq!(events, vec![]);
+                            }
+
+                            model.assert_matches(
+                                Balances::<Runtime>::iter(),
+                                TotalIssuance::<Runtime>::get(),
+                            );
+                        }
+                    });
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,21 @@
+//! Standing orders of the template2 currency, executed from the per-block agenda.
+
+use crate::pallet::Balance;
+use frame::prelude::*;
+
+/// A transfer of `amount` from `from` to `dest`, repeated every `period` blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ScheduledTransfer<AccountId, BlockNumber> {
+    /// The account that created the order and pays for it.
+    pub from: AccountId,
+    /// The account that receives every payment.
+    pub dest: AccountId,
+    /// Amount paid on every execution.
+    pub amount: Balance,
+    /// Blocks between two executions.
+    pub period: BlockNumber,
+    /// Executions left, including the one at `next`.
+    pub remaining: u32,
+    /// Block of the next execution, i.e. the agenda this order is currently queued in.
+    pub next: BlockNumber,
+}
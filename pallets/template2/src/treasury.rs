@@ -0,0 +1,31 @@
+//! Council-controlled spends out of the template2 treasury.
+
+use crate::pallet::{Balance, Config};
+use frame::prelude::*;
+
+/// A request to pay `amount` out of the treasury to `beneficiary`.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct SpendProposal<T: Config> {
+    /// The council member that made the proposal.
+    pub proposer: T::AccountId,
+    /// The account that is paid once the proposal has enough approvals.
+    pub beneficiary: T::AccountId,
+    /// Amount to pay out.
+    pub amount: Balance,
+    /// What the proposer put up in the bond account, returned once the spend is paid out.
+    pub bond: Balance,
+    /// Council members that approved so far, including the proposer. The approval that reaches
+    /// `T::SpendThreshold` pays the spend out instead of being stored.
+    pub approvals: BoundedVec<T::AccountId, T::SpendThreshold>,
+}
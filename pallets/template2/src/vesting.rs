@@ -0,0 +1,42 @@
+//! Linear vesting schedules used by the template2 currency.
+
+use crate::pallet::Balance;
+use frame::{
+    arithmetic::{AtLeast32BitUnsigned, SaturatedConversion},
+    prelude::*,
+};
+
+/// A schedule that unlocks `locked` linearly, `per_block` at a time, from `starting_block`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VestingInfo<BlockNumber> {
+    /// Amount locked when the schedule starts.
+    pub locked: Balance,
+    /// Amount that unlocks on every block after `starting_block`.
+    pub per_block: Balance,
+    /// Block from which unlocking begins.
+    pub starting_block: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> VestingInfo<BlockNumber> {
+    pub fn new(locked: Balance, per_block: Balance, starting_block: BlockNumber) -> Self {
+        Self {
+            locked,
+            per_block,
+            starting_block,
+        }
+    }
+
+    /// A schedule is only valid if it locks something and eventually unlocks all of it.
+    pub fn is_valid(&self) -> bool {
+        self.locked > 0 && self.per_block > 0
+    }
+
+    /// Amount that is still locked at block `n`.
+    pub fn locked_at(&self, n: BlockNumber) -> Balance {
+        let vested_blocks = n
+            .saturating_sub(self.starting_block)
+            .saturated_into::<Balance>();
+        self.locked
+            .saturating_sub(vested_blocks.saturating_mul(self.per_block))
+    }
+}
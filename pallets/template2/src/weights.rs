@@ -0,0 +1,125 @@
+//! Weights of the template2 currency.
+//!
+//! These are estimates: the storage items each call reads and writes in its worst case, listed
+//! next to every function, plus a fixed execution time. Regenerate them on reference hardware
+//! with the benchmarks in `benchmarking.rs`:
+//!
+//! ```sh
+//! minimal-template-node benchmark pallet --chain dev --pallet pallet_minimal_template2 \
+//!     --extrinsic '*' --output pallets/template2/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+
+/// The weights of the calls and hooks of the pallet.
+pub trait WeightInfo {
+    fn mint_unsafe() -> Weight;
+    fn transfer() -> Weight;
+    fn vested_transfer() -> Weight;
+    fn vest() -> Weight;
+    fn schedule_transfer() -> Weight;
+    fn cancel_scheduled_transfer() -> Weight;
+    fn propose_spend() -> Weight;
+    fn approve_spend() -> Weight;
+    fn reject_spend() -> Weight;
+    fn on_initialize(n: u32) -> Weight;
+}
+
+/// The weights of the pallet, using the runtime's database weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// `TotalIssuance`, `IssuanceHistory`, and the balance, history and system account of `dest`.
+    fn mint_unsafe() -> Weight {
+        Weight::from_parts(25_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(5, 5))
+    }
+    /// The balance, lock, history and system account of the sender, the treasury and `dest`.
+    fn transfer() -> Weight {
+        Weight::from_parts(45_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(10, 9))
+    }
+    /// A `transfer`, plus the vesting schedule and lock of `dest`.
+    fn vested_transfer() -> Weight {
+        Weight::from_parts(55_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(11, 11))
+    }
+    /// The vesting schedule, lock and system account of the caller.
+    fn vest() -> Weight {
+        Weight::from_parts(20_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 3))
+    }
+    /// The balance, lock and order count of the sender, the next id, the agenda and the order.
+    fn schedule_transfer() -> Weight {
+        Weight::from_parts(30_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(5, 4))
+    }
+    /// The order, the order count of its owner and the agenda it is queued in.
+    fn cancel_scheduled_transfer() -> Weight {
+        Weight::from_parts(25_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 3))
+    }
+    /// The council, the proposal count, the next id, the proposal and moving the bond, plus
+    /// paying it out of the treasury and returning the bond.
+    fn propose_spend() -> Weight {
+        Weight::from_parts(70_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(23, 20))
+    }
+    /// The council, the proposal and its count, plus paying it out of the treasury and returning
+    /// the bond.
+    fn approve_spend() -> Weight {
+        Weight::from_parts(60_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(17, 14))
+    }
+    /// The proposal and its count, and the balance, lock and history of the bond account and the
+    /// treasury.
+    fn reject_spend() -> Weight {
+        Weight::from_parts(30_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(7, 6))
+    }
+    /// The agenda, plus a `transfer`, the order and the agenda it is re-queued in for each of
+    /// the `n` orders executed.
+    fn on_initialize(n: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(
+                T::DbWeight::get()
+                    .reads_writes(12, 11)
+                    .saturating_mul(n.into()),
+            )
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn mint_unsafe() -> Weight {
+        Weight::from_parts(25_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(5, 5))
+    }
+    fn transfer() -> Weight {
+        Weight::from_parts(45_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(10, 9))
+    }
+    fn vested_transfer() -> Weight {
+        Weight::from_parts(55_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(11, 11))
+    }
+    fn vest() -> Weight {
+        Weight::from_parts(20_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 3))
+    }
+    fn schedule_transfer() -> Weight {
+        Weight::from_parts(30_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(5, 4))
+    }
+    fn cancel_scheduled_transfer() -> Weight {
+        Weight::from_parts(25_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 3))
+    }
+    fn propose_spend() -> Weight {
+        Weight::from_parts(70_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(23, 20))
+    }
+    fn approve_spend() -> Weight {
+        Weight::from_parts(60_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(17, 14))
+    }
+    fn reject_spend() -> Weight {
+        Weight::from_parts(30_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(7, 6))
+    }
+    fn on_initialize(n: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+            .saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(
+                RocksDbWeight::get()
+                    .reads_writes(12, 11)
+                    .saturating_mul(n.into()),
+            )
+    }
+}
@@ -19,5 +19,11 @@ fn main() {
 	#[cfg(feature = "std")]
 	{
 		substrate_wasm_builder::WasmBuilder::build_using_defaults();
+
+		#[cfg(feature = "spec-version-incremented-wasm")]
+		substrate_wasm_builder::WasmBuilder::init_with_defaults()
+			.enable_feature("increment-spec-version")
+			.set_file_name("wasm_binary_spec_version_incremented.rs")
+			.build();
 	}
 }
@@ -0,0 +1,109 @@
+//! The members of the council that approves spends out of the template2 treasury.
+
+// Re-export all pallet parts, this is needed to properly import the pallet into the runtime.
+pub use pallet::*;
+
+#[frame::pallet]
+pub mod pallet {
+    use alloc::vec::Vec;
+    #[cfg(feature = "try-runtime")]
+    use frame::deps::sp_runtime::TryRuntimeError;
+    use frame::{prelude::*, traits::SortedMembers};
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The origin that may replace the members.
+        type SetMembersOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// The most members the council can have.
+        #[pallet::constant]
+        type MaxMembers: Get<u32>;
+    }
+
+    /// The members of the council, sorted and without duplicates.
+    #[pallet::storage]
+    pub type Members<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxMembers>, ValueQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// The initial members of the council.
+        pub members: Vec<T::AccountId>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            Members::<T>::put(
+                Pallet::<T>::sorted(self.members.clone())
+                    .expect("too many genesis council members"),
+            );
+        }
+    }
+
+    // Pallets use events to inform users when important changes are made.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The members of the council were replaced.
+        MembersSet { members: Vec<T::AccountId> },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// More members than `T::MaxMembers`.
+        TooManyMembers,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            let members = Members::<T>::get();
+            ensure!(
+                members.windows(2).all(|w| w[0] < w[1]),
+                "council members not sorted and unique"
+            );
+            Ok(())
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Replace the members of the council with `members`.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_members(origin: OriginFor<T>, members: Vec<T::AccountId>) -> DispatchResult {
+            T::SetMembersOrigin::ensure_origin(origin)?;
+            let members = Self::sorted(members)?;
+            Members::<T>::put(&members);
+            Self::deposit_event(Event::MembersSet {
+                members: members.into_inner(),
+            });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// `members` sorted and without duplicates.
+        fn sorted(
+            mut members: Vec<T::AccountId>,
+        ) -> Result<BoundedVec<T::AccountId, T::MaxMembers>, Error<T>> {
+            members.sort();
+            members.dedup();
+            members.try_into().map_err(|_| Error::<T>::TooManyMembers)
+        }
+    }
+
+    impl<T: Config> SortedMembers<T::AccountId> for Pallet<T> {
+        fn sorted_members() -> Vec<T::AccountId> {
+            Members::<T>::get().into_inner()
+        }
+    }
+}
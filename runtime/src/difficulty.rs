@@ -0,0 +1,111 @@
+//! The proof-of-work difficulty, adjusted after every block towards a target block time.
+
+// Re-export all pallet parts, this is needed to properly import the pallet into the runtime.
+pub use pallet::*;
+
+#[frame::pallet]
+pub mod pallet {
+    #[cfg(feature = "try-runtime")]
+    use frame::deps::sp_runtime::TryRuntimeError;
+    use frame::{deps::sp_core::U256, prelude::*};
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_timestamp::Config<Moment = u64> {
+        /// The time between blocks, in milliseconds, that the difficulty steers towards.
+        #[pallet::constant]
+        type TargetBlockTime: Get<u64>;
+        /// The difficulty of the first blocks, and the lowest it ever adjusts to.
+        #[pallet::constant]
+        type MinimumDifficulty: Get<U256>;
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultDifficulty<T: Config>() -> U256 {
+        T::MinimumDifficulty::get()
+    }
+
+    /// The difficulty that the seal of the next block must meet.
+    #[pallet::storage]
+    pub type Difficulty<T> = StorageValue<_, U256, ValueQuery, DefaultDifficulty<T>>;
+
+    /// The timestamp of the previous block, if any.
+    #[pallet::storage]
+    pub type LastTimestamp<T> = StorageValue<_, u64, OptionQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            // `on_finalize` reads the timestamp, the last timestamp and the difficulty, and writes
+            // the latter two.
+            T::DbWeight::get().reads_writes(3, 2)
+        }
+
+        fn on_finalize(_n: BlockNumberFor<T>) {
+            let now = pallet_timestamp::Now::<T>::get();
+            if let Some(last) = LastTimestamp::<T>::get() {
+                Difficulty::<T>::put(Self::adjust(
+                    Difficulty::<T>::get(),
+                    now.saturating_sub(last),
+                ));
+            }
+            LastTimestamp::<T>::put(now);
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            ensure!(
+                Difficulty::<T>::get() >= T::MinimumDifficulty::get(),
+                "difficulty below the minimum"
+            );
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The difficulty of the next block.
+        pub fn difficulty() -> U256 {
+            Difficulty::<T>::get()
+        }
+
+        /// Scale `difficulty` by how much faster than `T::TargetBlockTime` the last block was
+        /// sealed in `block_time`, by at most a factor of 2 either way.
+        pub fn adjust(difficulty: U256, block_time: u64) -> U256 {
+            let target = U256::from(T::TargetBlockTime::get());
+            let adjusted = difficulty.saturating_mul(target) / U256::from(block_time.max(1));
+            adjusted
+                .clamp(difficulty / 2, difficulty.saturating_mul(2.into()))
+                .max(T::MinimumDifficulty::get())
+        }
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+    use super::*;
+    use frame::{deps::frame_benchmarking::v2::*, prelude::*};
+
+    #[benchmarks]
+    mod benchmarks {
+        use super::*;
+
+        /// Finalizing a block that follows another one, and so adjusts the difficulty.
+        #[benchmark]
+        fn on_finalize() {
+            let now = T::TargetBlockTime::get();
+            LastTimestamp::<T>::put(0);
+            pallet_timestamp::Now::<T>::put(now);
+
+            #[block]
+            {
+                Pallet::<T>::on_finalize(frame_system::Pallet::<T>::block_number());
+            }
+
+            assert_eq!(LastTimestamp::<T>::get(), Some(now));
+        }
+
+        impl_benchmark_test_suite!(Pallet, crate::tests::development_ext(), crate::Runtime);
+    }
+}
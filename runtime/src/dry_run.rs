@@ -0,0 +1,53 @@
+//! Simulation of calls, for clients that want to know the effects of a call before signing it.
+
+use crate::{interface, OriginCaller, RuntimeCall, RuntimeEvent, RuntimeOrigin, System};
+use alloc::vec::Vec;
+use frame::{
+    deps::frame_support::{
+        dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
+        storage::{with_transaction_unchecked, TransactionOutcome},
+        storage_alias,
+    },
+    prelude::*,
+    traits::Dispatchable,
+};
+
+/// The effects of dispatching a call, as returned by `DryRunApi::dry_run_call`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CallDryRunEffects<Event> {
+    /// The result of the dispatch.
+    pub execution_result: DispatchResultWithPostInfo,
+    /// The events emitted by the dispatch, oldest first.
+    pub emitted_events: Vec<Event>,
+    /// The weight actually used by the dispatch.
+    pub weight: Weight,
+}
+
+/// The current block number of `frame_system`, which only lets tests set it.
+#[storage_alias]
+type Number = StorageValue<System, interface::BlockNumber, ValueQuery>;
+
+/// Dispatch `call` from `origin` as if at the start of the next block, and roll back all of its
+/// effects.
+pub fn dry_run_call(origin: OriginCaller, call: RuntimeCall) -> CallDryRunEffects<RuntimeEvent> {
+    with_transaction_unchecked(|| {
+        // no events are deposited at genesis, and the ones of the current block are not wanted.
+        Number::mutate(|n| *n += 1);
+        System::reset_events();
+
+        let info = call.get_dispatch_info();
+        let execution_result = call.dispatch(RuntimeOrigin::from(origin));
+        let post_info = match execution_result {
+            Ok(post_info) => post_info,
+            Err(e) => e.post_info,
+        };
+
+        TransactionOutcome::Rollback(CallDryRunEffects {
+            execution_result,
+            emitted_events: System::read_events_no_consensus()
+                .map(|record| record.event)
+                .collect(),
+            weight: post_info.calc_actual_weight(&info),
+        })
+    })
+}
@@ -0,0 +1,201 @@
+//! Routing of transaction fees and tips between a burn, a treasury and the block author.
+
+// Re-export all pallet parts, this is needed to properly import the pallet into the runtime.
+pub use pallet::*;
+
+#[frame::pallet]
+pub mod pallet {
+    use frame::{
+        arithmetic::{Percent, Zero},
+        prelude::*,
+        traits::{
+            fungible::{Balanced, Credit},
+            Imbalance, OnUnbalanced,
+        },
+    };
+
+    pub type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
+    pub type CreditOf<T> =
+        Credit<<T as frame_system::Config>::AccountId, pallet_balances::Pallet<T>>;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_balances::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The part of every fee that is burned.
+        #[pallet::constant]
+        type FeeBurn: Get<Percent>;
+        /// The part of every fee that is paid to `Treasury`. The rest goes to the block author.
+        #[pallet::constant]
+        type FeeToTreasury: Get<Percent>;
+        /// The part of every tip that is burned.
+        #[pallet::constant]
+        type TipBurn: Get<Percent>;
+        /// The part of every tip that is paid to `Treasury`. The rest goes to the block author.
+        #[pallet::constant]
+        type TipToTreasury: Get<Percent>;
+
+        /// The account that collects the treasury's part. Without one, that part is burned.
+        type Treasury: Get<Option<Self::AccountId>>;
+        /// The author of the current block, if known. Without one, its part goes to `Treasury`.
+        type BlockAuthor: Get<Option<Self::AccountId>>;
+    }
+
+    // Pallets use events to inform users when important changes are made.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The fee of a transaction was split.
+        FeeSplit {
+            burned: BalanceOf<T>,
+            to_treasury: BalanceOf<T>,
+            to_author: BalanceOf<T>,
+            author: Option<T::AccountId>,
+        },
+        /// The tip of a transaction was split.
+        TipSplit {
+            burned: BalanceOf<T>,
+            to_treasury: BalanceOf<T>,
+            to_author: BalanceOf<T>,
+            author: Option<T::AccountId>,
+        },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            let fee = T::FeeBurn::get().deconstruct() + T::FeeToTreasury::get().deconstruct();
+            let tip = T::TipBurn::get().deconstruct() + T::TipToTreasury::get().deconstruct();
+            assert!(fee <= 100, "fee split exceeds 100%");
+            assert!(tip <= 100, "tip split exceeds 100%");
+        }
+    }
+
+    /// The parts of a credit after a split, as `(burned, to_treasury, to_author, author)`.
+    type Split<T> = (
+        BalanceOf<T>,
+        BalanceOf<T>,
+        BalanceOf<T>,
+        Option<<T as frame_system::Config>::AccountId>,
+    );
+
+    impl<T: Config> Pallet<T> {
+        /// Burn `burn` of `credit`, and pay `to_treasury` of it to `T::Treasury` and the rest to
+        /// the block author.
+        ///
+        /// A part that cannot be paid, e.g. because it would not create its account or there is
+        /// no treasury, is burned.
+        fn split(credit: CreditOf<T>, burn: Percent, to_treasury: Percent) -> Split<T> {
+            let total = credit.peek();
+            let (_burned, rest) = credit.split(burn.mul_floor(total));
+            let (treasury_part, author_part) = rest.split(to_treasury.mul_floor(total));
+
+            let author = T::BlockAuthor::get();
+            let (to_treasury, to_author) = match &author {
+                Some(author) => (
+                    Self::pay_treasury(treasury_part),
+                    Self::pay(author, author_part),
+                ),
+                None => (
+                    Self::pay_treasury(treasury_part.merge(author_part)),
+                    Zero::zero(),
+                ),
+            };
+
+            (
+                total - to_treasury - to_author,
+                to_treasury,
+                to_author,
+                author,
+            )
+        }
+
+        /// Pay `credit` to `T::Treasury`, or burn it if there is none.
+        fn pay_treasury(credit: CreditOf<T>) -> BalanceOf<T> {
+            match T::Treasury::get() {
+                Some(treasury) => Self::pay(&treasury, credit),
+                None => Zero::zero(),
+            }
+        }
+
+        /// Pay `credit` to `who`, returning how much was paid.
+        fn pay(who: &T::AccountId, credit: CreditOf<T>) -> BalanceOf<T> {
+            let amount = credit.peek();
+            match pallet_balances::Pallet::<T>::resolve(who, credit) {
+                Ok(()) => amount,
+                Err(_) => Zero::zero(),
+            }
+        }
+    }
+
+    /// Splits the fee and the tip of every transaction as configured in [`Config`].
+    pub struct DealWithFees<T>(PhantomData<T>);
+
+    impl<T: Config> OnUnbalanced<CreditOf<T>> for DealWithFees<T> {
+        fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = CreditOf<T>>) {
+            if let Some(fees) = fees_then_tips.next().filter(|c| !c.peek().is_zero()) {
+                let (burned, to_treasury, to_author, author) =
+                    Pallet::<T>::split(fees, T::FeeBurn::get(), T::FeeToTreasury::get());
+                Pallet::<T>::deposit_event(Event::FeeSplit {
+                    burned,
+                    to_treasury,
+                    to_author,
+                    author,
+                });
+            }
+
+            if let Some(tips) = fees_then_tips.next().filter(|c| !c.peek().is_zero()) {
+                let (burned, to_treasury, to_author, author) =
+                    Pallet::<T>::split(tips, T::TipBurn::get(), T::TipToTreasury::get());
+                Pallet::<T>::deposit_event(Event::TipSplit {
+                    burned,
+                    to_treasury,
+                    to_author,
+                    author,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+    use super::*;
+    use frame::{
+        arithmetic::Saturating,
+        deps::{codec::Encode, frame_benchmarking::v2::*, sp_runtime::DigestItem},
+        prelude::*,
+        traits::{fungible::Balanced, OnUnbalanced},
+    };
+    use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+
+    #[benchmarks]
+    mod benchmarks {
+        use super::*;
+
+        /// Splitting the fee and the tip of a transaction, each between a burn, the treasury and
+        /// a known block author.
+        #[benchmark]
+        fn on_unbalanceds() {
+            // the author of an aura slot is one of the authorities.
+            frame_system::Pallet::<T>::deposit_log(DigestItem::PreRuntime(
+                AURA_ENGINE_ID,
+                Slot::from(0).encode(),
+            ));
+            let amount = T::ExistentialDeposit::get().saturating_mul(1_000u32.into());
+            let fee = pallet_balances::Pallet::<T>::issue(amount);
+            let tip = pallet_balances::Pallet::<T>::issue(amount);
+
+            #[block]
+            {
+                DealWithFees::<T>::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
+            }
+        }
+
+        impl_benchmark_test_suite!(Pallet, crate::tests::development_ext(), crate::Runtime);
+    }
+}
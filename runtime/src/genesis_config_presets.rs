@@ -0,0 +1,123 @@
+//! The genesis presets of the runtime, selected by name through `GenesisBuilder::get_preset`.
+
+use crate::{
+    interface, AuraConfig, AuraId, BabeConfig, BabeId, BalancesConfig, CouncilConfig,
+    CurrencyConfig, GrandpaConfig, GrandpaId, SudoConfig, BABE_GENESIS_EPOCH_CONFIG,
+};
+use alloc::{vec, vec::Vec};
+use serde_json::{json, Value};
+use sp_genesis_builder::PresetId;
+use sp_keyring::{AccountKeyring, Ed25519Keyring};
+
+/// A single node chain, where every dev account is endowed and Alice is the only authority.
+pub const DEVELOPMENT: &str = "development";
+/// A local multi-node chain, where the first six dev accounts are endowed and Alice and Bob are
+/// the authorities.
+pub const LOCAL_TESTNET: &str = "local_testnet";
+/// A live chain skeleton without any keys: the sudo key, the council, the authorities and the
+/// endowed accounts are left empty, to be filled into the spec exported with
+/// `build-spec --chain staging`.
+pub const STAGING: &str = "staging";
+
+/// The aura, grandpa and babe keys of `who`, as inserted by the node with e.g. `--alice`.
+fn authority_keys(who: AccountKeyring) -> (AuraId, GrandpaId, BabeId) {
+    let grandpa = Ed25519Keyring::iter()
+        .find(|k| k.to_seed() == who.to_seed())
+        .expect("every sr25519 dev account has an ed25519 twin; qed");
+    (
+        who.public().into(),
+        grandpa.public().into(),
+        who.public().into(),
+    )
+}
+
+/// Configure `endowed` in `Balances` and `Currency`, with `root` as the sudo key and the only
+/// council member, and `authorities` as the aura, grandpa and babe authorities.
+fn genesis(
+    endowed: Vec<AccountKeyring>,
+    root: AccountKeyring,
+    authorities: Vec<AccountKeyring>,
+) -> Value {
+    let (mut aura, mut grandpa, mut babe) = (Vec::new(), Vec::new(), Vec::new());
+    for (aura_id, grandpa_id, babe_id) in authorities.into_iter().map(authority_keys) {
+        aura.push(aura_id);
+        grandpa.push((grandpa_id, 1));
+        babe.push((babe_id, 1));
+    }
+
+    // enough to pay fees for a very long time.
+    let endowment: interface::Balance = 1 << 60;
+    let currency_endowment = interface::CurrencyBalance::from(endowment);
+
+    json!({
+        "balances": BalancesConfig {
+            balances: endowed.iter().map(|a| (a.to_account_id(), endowment)).collect(),
+        },
+        "sudo": SudoConfig { key: Some(root.to_account_id()) },
+        "council": CouncilConfig { members: vec![root.to_account_id()] },
+        "aura": AuraConfig { authorities: aura },
+        "grandpa": GrandpaConfig { authorities: grandpa, ..Default::default() },
+        "babe": BabeConfig {
+            authorities: babe,
+            epoch_config: BABE_GENESIS_EPOCH_CONFIG,
+            ..Default::default()
+        },
+        "currency": CurrencyConfig {
+            balances: endowed.iter().map(|a| (a.to_account_id(), currency_endowment)).collect(),
+            ..Default::default()
+        },
+    })
+}
+
+fn development_genesis() -> Value {
+    genesis(
+        AccountKeyring::iter().collect(),
+        AccountKeyring::Alice,
+        vec![AccountKeyring::Alice],
+    )
+}
+
+fn local_testnet_genesis() -> Value {
+    genesis(
+        vec![
+            AccountKeyring::Alice,
+            AccountKeyring::Bob,
+            AccountKeyring::Charlie,
+            AccountKeyring::Dave,
+            AccountKeyring::Eve,
+            AccountKeyring::Ferdie,
+        ],
+        AccountKeyring::Alice,
+        vec![AccountKeyring::Alice, AccountKeyring::Bob],
+    )
+}
+
+/// Only what doesn't depend on the operators' keys. Without grandpa authorities the node refuses
+/// to start, so a launch can't silently happen with placeholder keys.
+fn staging_genesis() -> Value {
+    json!({
+        "sudo": SudoConfig { key: None },
+        "babe": BabeConfig { epoch_config: BABE_GENESIS_EPOCH_CONFIG, ..Default::default() },
+    })
+}
+
+/// The genesis config patch of the preset `id`, if there is one.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+    let patch = match core::str::from_utf8(id.as_ref()) {
+        Ok(DEVELOPMENT) => development_genesis(),
+        Ok(LOCAL_TESTNET) => local_testnet_genesis(),
+        Ok(STAGING) => staging_genesis(),
+        _ => return None,
+    };
+
+    Some(
+        serde_json::to_string(&patch)
+            .expect("serialization to json is expected to work; qed")
+            .into_bytes(),
+    )
+}
+
+/// The names of all presets.
+pub fn preset_names() -> Vec<PresetId> {
+    vec![DEVELOPMENT.into(), LOCAL_TESTNET.into(), STAGING.into()]
+}
@@ -4,14 +4,38 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+/// The WASM binary of this runtime built with `increment-spec-version`, which the node's upgrade
+/// tests upgrade to.
+#[cfg(all(feature = "std", feature = "spec-version-incremented-wasm"))]
+pub mod wasm_spec_version_incremented {
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/wasm_binary_spec_version_incremented.rs"
+    ));
+}
+
 extern crate alloc;
 
-use alloc::{vec, vec::Vec};
+pub mod council;
+pub mod difficulty;
+pub mod dry_run;
+pub mod fees;
+pub mod genesis_config_presets;
+
+use alloc::vec::Vec;
 use frame::{
-    deps::frame_support::{
-        genesis_builder_helper::{build_state, get_preset},
-        runtime,
-        weights::{FixedFee, NoFee},
+    arithmetic::{Bounded, FixedPointNumber, Perbill, Percent, Permill, Perquintill},
+    deps::{
+        frame_support::{
+            genesis_builder_helper::{build_state, get_preset},
+            runtime,
+            weights::{
+                constants::ExtrinsicBaseWeight, ConstantMultiplier, WeightToFeeCoefficient,
+                WeightToFeeCoefficients, WeightToFeePolynomial,
+            },
+            PalletId,
+        },
+        sp_core::{sr25519, Void, U256},
     },
     prelude::*,
     runtime::{
@@ -21,12 +45,19 @@ use frame::{
         },
         prelude::*,
     },
+    traits::{AccountIdConversion, FindAuthor, PalletsInfoAccess},
 };
-use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
-use sp_runtime::RuntimeString;
+use pallet_transaction_payment::{
+    FeeDetails, Multiplier, RuntimeDispatchInfo, TargetedFeeAdjustment,
+};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_runtime::{impl_opaque_keys, ConsensusEngineId, RuntimeString};
 
 #[rustfmt::skip]
 /// The runtime version.
+#[cfg(not(feature = "increment-spec-version"))]
 #[runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name           : create_runtime_str!("minimal-template-runtime"),
@@ -39,8 +70,38 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     state_version       : 1,
 };
 
+#[rustfmt::skip]
+/// The runtime version, with the `spec_version` of the next release.
+#[cfg(feature = "increment-spec-version")]
+#[runtime_version]
+pub const VERSION: RuntimeVersion = RuntimeVersion {
+    spec_name           : create_runtime_str!("minimal-template-runtime"),
+    impl_name           : create_runtime_str!("minimal-template-runtime"),
+    authoring_version   : 1,
+    spec_version        : 3,
+    impl_version        : 1,
+    apis                : RUNTIME_API_VERSIONS,
+    transaction_version : 1,
+    state_version       : 1,
+};
+
 pub const TOKEN_SYMBOL: RuntimeString = create_runtime_str!("ℵ");
 pub const TOKEN_NAME: RuntimeString = create_runtime_str!("XYZ");
+pub const TOKEN_DECIMALS: u8 = 12;
+pub const SS58_PREFIX: u16 = 42;
+/// The duration of an aura slot, and so the block time of the `aura-grandpa` consensus.
+pub const SLOT_DURATION: u64 = 6000;
+/// The block time that the proof-of-work difficulty of the `pow` consensus steers towards.
+pub const POW_TARGET_BLOCK_TIME: u64 = 6000;
+/// The number of babe slots in an epoch, an hour at 6s slots.
+pub const EPOCH_DURATION: u64 = 600;
+/// The babe epoch configuration of genesis: a primary slot leader in about a quarter of the slots,
+/// and a secondary one in all the others.
+pub const BABE_GENESIS_EPOCH_CONFIG: sp_consensus_babe::BabeEpochConfiguration =
+    sp_consensus_babe::BabeEpochConfiguration {
+        c: (1, 4),
+        allowed_slots: sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryPlainSlots,
+    };
 
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
@@ -108,6 +169,27 @@ mod runtime {
     /// A minimal pallet template.
     #[runtime::pallet_index(5)]
     pub type Template           = pallet_minimal_template::Pallet<Runtime>;
+    /// A second, self-contained currency with vesting, standing orders and a treasury.
+    #[runtime::pallet_index(6)]
+    pub type Currency           = pallet_minimal_template2::Pallet<Runtime>;
+    /// Splits transaction fees and tips between a burn, the treasury and the block author.
+    #[runtime::pallet_index(7)]
+    pub type Fees               = fees::Pallet<Runtime>;
+    /// The authorities of the aura slots, when blocks are authored with aura.
+    #[runtime::pallet_index(8)]
+    pub type Aura               = pallet_aura::Pallet<Runtime>;
+    /// The authorities of the GRANDPA finality gadget.
+    #[runtime::pallet_index(9)]
+    pub type Grandpa            = pallet_grandpa::Pallet<Runtime>;
+    /// The proof-of-work difficulty, when blocks are mined.
+    #[runtime::pallet_index(10)]
+    pub type Difficulty         = difficulty::Pallet<Runtime>;
+    /// The epochs, authorities and randomness of babe, when blocks are authored with babe.
+    #[runtime::pallet_index(11)]
+    pub type Babe               = pallet_babe::Pallet<Runtime>;
+    /// The council that approves spends out of the template2 treasury.
+    #[runtime::pallet_index(12)]
+    pub type Council            = council::Pallet<Runtime>;
 }
 
 parameter_types! {
@@ -120,6 +202,7 @@ parameter_types! {
 impl frame_system::Config for Runtime {
     type Block          = Block;
     type Version        = Version;
+    type SS58Prefix     = ConstU16<SS58_PREFIX>;
     // Use the account data from the balances pallet
     type AccountData    = pallet_balances::AccountData<<Runtime as pallet_balances::Config>::Balance>;
 }
@@ -138,20 +221,228 @@ impl pallet_sudo::Config for Runtime {}
 #[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
 impl pallet_timestamp::Config for Runtime {}
 
+#[rustfmt::skip]
+// Implements the types required for the aura pallet. The seal consensus modes author blocks
+// without slots, so the timestamp is not checked against the slot here, but by the aura import
+// queue of the `aura-grandpa` mode.
+impl pallet_aura::Config for Runtime {
+    type AuthorityId                = AuraId;
+    type DisabledValidators         = ();
+    type MaxAuthorities             = ConstU32<32>;
+    type AllowMultipleBlocksPerSlot = ConstBool<false>;
+    type SlotDuration               = ConstU64<SLOT_DURATION>;
+}
+
+#[rustfmt::skip]
+// Implements the types required for the grandpa pallet. There are no sessions nor staking, so
+// equivocations cannot be reported.
+impl pallet_grandpa::Config for Runtime {
+    type RuntimeEvent             = RuntimeEvent;
+    type WeightInfo               = ();
+    type MaxAuthorities           = ConstU32<32>;
+    type MaxNominators            = ConstU32<0>;
+    type MaxSetIdSessionEntries   = ConstU64<0>;
+    type KeyOwnerProof            = Void;
+    type EquivocationReportSystem = ();
+}
+
+parameter_types! {
+    /// Low enough for a single CPU thread to mine the first blocks of a dev chain quickly.
+    pub MinimumDifficulty: U256 = U256::from(100_000);
+}
+
+#[rustfmt::skip]
+// Implements the types required for the difficulty pallet.
+impl difficulty::Config for Runtime {
+    type TargetBlockTime   = ConstU64<POW_TARGET_BLOCK_TIME>;
+    type MinimumDifficulty = MinimumDifficulty;
+}
+
+#[rustfmt::skip]
+// Implements the types required for the babe pallet. Like aura, babe's slots are not checked
+// against the timestamp here, as the seal consensus modes author blocks without slots, and there
+// are no sessions to rotate its authorities in.
+impl pallet_babe::Config for Runtime {
+    type EpochDuration            = ConstU64<EPOCH_DURATION>;
+    type ExpectedBlockTime        = ConstU64<SLOT_DURATION>;
+    type EpochChangeTrigger       = pallet_babe::SameAuthoritiesForever;
+    type DisabledValidators       = ();
+    type WeightInfo               = ();
+    type MaxAuthorities           = ConstU32<32>;
+    type MaxNominators            = ConstU32<0>;
+    type KeyOwnerProof            = Void;
+    type EquivocationReportSystem = ();
+}
+
+/// On-chain randomness for pallets, from the babe VRF outputs of the epoch before the previous
+/// one. It only changes while blocks are authored with babe.
+pub type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
+
+impl_opaque_keys! {
+    /// The keys of a validator: an aura or babe key to author blocks and a grandpa key to vote on
+    /// finality.
+    pub struct SessionKeys {
+        pub aura: Aura,
+        pub grandpa: Grandpa,
+        pub babe: Babe,
+    }
+}
+
+parameter_types! {
+    /// The fee of an extrinsic that weighs `ExtrinsicBaseWeight`, before the multiplier.
+    pub const ExtrinsicBaseFee: interface::Balance = 10_000_000;
+    /// The fee of every byte of an encoded extrinsic.
+    pub const TransactionByteFee: interface::Balance = 10_000;
+    /// The block fullness the fee multiplier steers towards.
+    pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+    /// How fast the fee multiplier reacts to blocks above or below the target.
+    pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+    /// The fee multiplier never drops below this, so that fees never become negligible.
+    pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10u128);
+    /// The fee multiplier is not capped.
+    pub MaximumMultiplier: Multiplier = Bounded::max_value();
+}
+
+/// Charges `ExtrinsicBaseFee` for every `ExtrinsicBaseWeight` of execution time.
+pub struct WeightToFee;
+impl WeightToFeePolynomial for WeightToFee {
+    type Balance = interface::Balance;
+
+    fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+        let p = ExtrinsicBaseFee::get();
+        let q = interface::Balance::from(ExtrinsicBaseWeight::get().ref_time());
+        [WeightToFeeCoefficient {
+            degree: 1,
+            negative: false,
+            coeff_frac: Perbill::from_rational(p % q, q),
+            coeff_integer: p / q,
+        }]
+        .into_iter()
+        .collect()
+    }
+}
+
+/// Adjusts the fee multiplier after every block, towards `TargetBlockFullness`.
+pub type FeeMultiplierUpdate = TargetedFeeAdjustment<
+    Runtime,
+    TargetBlockFullness,
+    AdjustmentVariable,
+    MinimumMultiplier,
+    MaximumMultiplier,
+>;
+
 #[rustfmt::skip]
 // Implements the types required for the transaction payment pallet.
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
 impl pallet_transaction_payment::Config for Runtime {
-    type OnChargeTransaction    = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
-    // Setting fee as independent of the weight of the extrinsic for demo purposes
-    type WeightToFee            = NoFee<<Self as pallet_balances::Config>::Balance>;
-    // Setting fee as fixed for any length of the call data for demo purposes
-    type LengthToFee            = FixedFee<1, <Self as pallet_balances::Config>::Balance>;
+    type OnChargeTransaction    = pallet_transaction_payment::FungibleAdapter<Balances, fees::DealWithFees<Runtime>>;
+    type WeightToFee            = WeightToFee;
+    type LengthToFee            = ConstantMultiplier<interface::Balance, TransactionByteFee>;
+    type FeeMultiplierUpdate    = FeeMultiplierUpdate;
 }
 
 // Implements the types required for the template pallet.
 impl pallet_minimal_template::Config for Runtime {
     type RuntimeEvent = <Runtime as frame_system::Config>::RuntimeEvent;
+    type WeightInfo = pallet_minimal_template::weights::SubstrateWeight<Runtime>;
+}
+
+#[rustfmt::skip]
+// Implements the types required for the council pallet: root sets at most 16 members.
+impl council::Config for Runtime {
+    type RuntimeEvent     = RuntimeEvent;
+    type SetMembersOrigin = EnsureRoot<interface::AccountId>;
+    type MaxMembers       = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const CurrencyPalletId: PalletId = PalletId(*b"py/trsry");
+    pub const CurrencyTransferFee: Permill = Permill::from_percent(1);
+    /// One unit of the currency, at the native token's decimals.
+    pub const CurrencySpendProposalBond: interface::CurrencyBalance = 10u128.pow(TOKEN_DECIMALS as u32);
+}
+
+#[rustfmt::skip]
+// Implements the types required for the template2 currency pallet.
+impl pallet_minimal_template2::Config for Runtime {
+    type RuntimeEvent           = RuntimeEvent;
+    type ExistentialDeposit     = ConstU128<1>;
+    type MaxScheduledPerBlock   = ConstU32<16>;
+    type MaxScheduledPerAccount = ConstU32<8>;
+    type PalletId               = CurrencyPalletId;
+    type TransferFee            = CurrencyTransferFee;
+    type Council                = Council;
+    type SpendThreshold         = ConstU32<1>;
+    type SpendProposalBond      = CurrencySpendProposalBond;
+    type MaxSpendProposals      = ConstU32<64>;
+    type RejectOrigin           = EnsureRoot<interface::AccountId>;
+    type MaxCheckpoints         = ConstU32<64>;
+    type MintOrigin             = EnsureRoot<interface::AccountId>;
+    type WeightInfo             = pallet_minimal_template2::weights::SubstrateWeight<Runtime>;
+}
+
+/// Finds the block author in the pre-runtime digests, as the account of the aura or babe authority
+/// of the slot. Blocks of the seal consensus modes have no author.
+pub struct AuthorFinder;
+impl FindAuthor<interface::AccountId> for AuthorFinder {
+    fn find_author<'a, I>(digests: I) -> Option<interface::AccountId>
+    where
+        I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+    {
+        let digests: Vec<_> = digests.into_iter().collect();
+        let aura = pallet_aura::FindAccountFromAuthorIndex::<Runtime, Aura>::find_author(
+            digests.iter().copied(),
+        )
+        .map(sr25519::Public::from);
+        let babe = || {
+            let index = Babe::find_author(digests.iter().copied())?;
+            let (authority, _) = pallet_babe::Authorities::<Runtime>::get()
+                .get(index as usize)?
+                .clone();
+            Some(sr25519::Public::from(authority))
+        };
+        aura.or_else(babe).map(Into::into)
+    }
+}
+
+/// The author of the current block, as found by `AuthorFinder`.
+pub struct BlockAuthor;
+impl Get<Option<interface::AccountId>> for BlockAuthor {
+    fn get() -> Option<interface::AccountId> {
+        let digest = System::digest();
+        AuthorFinder::find_author(digest.logs().iter().filter_map(|d| d.as_pre_runtime()))
+    }
+}
+
+parameter_types! {
+    pub const FeeBurn: Percent = Percent::from_percent(20);
+    pub const FeeToTreasury: Percent = Percent::from_percent(50);
+    pub const TipBurn: Percent = Percent::from_percent(0);
+    pub const TipToTreasury: Percent = Percent::from_percent(0);
+    pub const FeeTreasuryPalletId: PalletId = PalletId(*b"py/feetr");
+}
+
+/// The keyless account that collects the treasury's part of fees.
+///
+/// Not the template2 treasury account, which holds template2 funds rather than native ones.
+pub struct FeeTreasury;
+impl Get<Option<interface::AccountId>> for FeeTreasury {
+    fn get() -> Option<interface::AccountId> {
+        Some(FeeTreasuryPalletId::get().into_account_truncating())
+    }
+}
+
+#[rustfmt::skip]
+// Implements the types required for the fee routing pallet: 20% of every fee is burned, 50% goes
+// to the fee treasury and 30% to the block author, which also gets all tips.
+impl fees::Config for Runtime {
+    type RuntimeEvent  = RuntimeEvent;
+    type FeeBurn       = FeeBurn;
+    type FeeToTreasury = FeeToTreasury;
+    type TipBurn       = TipBurn;
+    type TipToTreasury = TipToTreasury;
+    type Treasury      = FeeTreasury;
+    type BlockAuthor   = BlockAuthor;
 }
 
 type Block = frame::runtime::types_common::BlockOf<Runtime, SignedExtra>;
@@ -160,10 +451,55 @@ type Header = HeaderFor<Runtime>;
 type RuntimeExecutive =
     Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
 
+#[cfg(feature = "runtime-benchmarks")]
+impl frame_system_benchmarking::Config for Runtime {}
+#[cfg(feature = "runtime-benchmarks")]
+impl frame_benchmarking::baseline::Config for Runtime {}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarks {
+    frame_benchmarking::define_benchmarks!(
+        [frame_benchmarking, BaselineBench::<Runtime>]
+        [frame_system, SystemBench::<Runtime>]
+        [pallet_balances, Balances]
+        [pallet_timestamp, Timestamp]
+        [pallet_minimal_template, Template]
+        [pallet_minimal_template2, Currency]
+        [fees, Fees]
+        [difficulty, Difficulty]
+    );
+}
+
 decl_runtime_apis! {
     pub trait ChainMetadata {
         fn token_name() -> RuntimeString;
         fn token_symbol() -> RuntimeString;
+        fn token_decimals() -> u8;
+        fn existential_deposit() -> interface::Balance;
+        fn ss58_prefix() -> u16;
+        /// The names of the pallets of the runtime, by index.
+        fn enabled_pallets() -> Vec<RuntimeString>;
+    }
+
+    /// Historical balances of the template2 currency, e.g. for snapshot voting.
+    pub trait CurrencyHistory {
+        /// `account`'s balance at the end of `block`, if it is still known.
+        fn balance_at(
+            account: interface::AccountId,
+            block: interface::BlockNumber,
+        ) -> Option<interface::CurrencyBalance>;
+        /// The total issuance at the end of `block`, if it is still known.
+        fn total_issuance_at(block: interface::BlockNumber) -> Option<interface::CurrencyBalance>;
+    }
+
+    /// Simulation of calls, e.g. to show their effects before they are signed.
+    pub trait DryRunApi {
+        /// Dispatch `call` from `origin` as if at the start of the next block, without
+        /// committing any of its effects.
+        fn dry_run_call(
+            origin: OriginCaller,
+            call: RuntimeCall,
+        ) -> dry_run::CallDryRunEffects<RuntimeEvent>;
     }
 }
 
@@ -227,13 +563,92 @@ impl_runtime_apis! {
     }
 
     impl apis::SessionKeys<Block> for Runtime {
-        fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
-            Default::default()
+        fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
+            SessionKeys::generate(seed)
         }
         fn decode_session_keys(
-            _encoded: Vec<u8>,
+            encoded: Vec<u8>,
         ) -> Option<Vec<(Vec<u8>, apis::KeyTypeId)>> {
-            Default::default()
+            SessionKeys::decode_into_raw_public_keys(&encoded)
+        }
+    }
+
+    impl apis::AuraApi<Block, AuraId> for Runtime {
+        fn slot_duration() -> sp_consensus_aura::SlotDuration {
+            sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
+        }
+        fn authorities() -> Vec<AuraId> {
+            pallet_aura::Authorities::<Runtime>::get().into_inner()
+        }
+    }
+
+    impl sp_consensus_babe::BabeApi<Block> for Runtime {
+        fn configuration() -> sp_consensus_babe::BabeConfiguration {
+            let epoch_config =
+                pallet_babe::EpochConfig::<Runtime>::get().unwrap_or(BABE_GENESIS_EPOCH_CONFIG);
+            sp_consensus_babe::BabeConfiguration {
+                // `Babe::slot_duration` is derived from the timestamp's minimum period, which is
+                // kept short for the seal consensus modes.
+                slot_duration: SLOT_DURATION,
+                epoch_length: EPOCH_DURATION,
+                c: epoch_config.c,
+                authorities: pallet_babe::Authorities::<Runtime>::get().into_inner(),
+                randomness: pallet_babe::Randomness::<Runtime>::get(),
+                allowed_slots: epoch_config.allowed_slots,
+            }
+        }
+        fn current_epoch_start() -> sp_consensus_babe::Slot {
+            Babe::current_epoch_start()
+        }
+        fn current_epoch() -> sp_consensus_babe::Epoch {
+            Babe::current_epoch()
+        }
+        fn next_epoch() -> sp_consensus_babe::Epoch {
+            Babe::next_epoch()
+        }
+        fn generate_key_ownership_proof(
+            _slot: sp_consensus_babe::Slot,
+            _authority_id: BabeId,
+        ) -> Option<sp_consensus_babe::OpaqueKeyOwnershipProof> {
+            // there are no sessions to prove the ownership of a key in.
+            None
+        }
+        fn submit_report_equivocation_unsigned_extrinsic(
+            _equivocation_proof: sp_consensus_babe::EquivocationProof<HeaderFor<Runtime>>,
+            _key_owner_proof: sp_consensus_babe::OpaqueKeyOwnershipProof,
+        ) -> Option<()> {
+            None
+        }
+    }
+
+    impl apis::GrandpaApi<Block> for Runtime {
+        fn grandpa_authorities() -> sp_consensus_grandpa::AuthorityList {
+            Grandpa::grandpa_authorities()
+        }
+        fn current_set_id() -> sp_consensus_grandpa::SetId {
+            Grandpa::current_set_id()
+        }
+        fn submit_report_equivocation_unsigned_extrinsic(
+            _equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+                interface::Hash,
+                interface::BlockNumber,
+            >,
+            _key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+        ) -> Option<()> {
+            None
+        }
+        fn generate_key_ownership_proof(
+            _set_id: sp_consensus_grandpa::SetId,
+            _authority_id: GrandpaId,
+        ) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
+            // there are no sessions to prove the ownership of a key in.
+            None
+        }
+    }
+
+    impl sp_consensus_pow::DifficultyApi<Block, U256> for Runtime {
+        fn difficulty() -> U256 {
+            Difficulty::difficulty()
         }
     }
 
@@ -266,10 +681,10 @@ impl_runtime_apis! {
             build_state::<RuntimeGenesisConfig>(config)
         }
         fn get_preset(id: &Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
-            get_preset::<RuntimeGenesisConfig>(id, |_| None)
+            get_preset::<RuntimeGenesisConfig>(id, genesis_config_presets::get_preset)
         }
         fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
-            vec![]
+            genesis_config_presets::preset_names()
         }
     }
 
@@ -280,6 +695,97 @@ impl_runtime_apis! {
         fn token_symbol() -> RuntimeString {
             TOKEN_SYMBOL
         }
+        fn token_decimals() -> u8 {
+            TOKEN_DECIMALS
+        }
+        fn existential_deposit() -> interface::Balance {
+            <interface::MinimumBalance as Get<interface::Balance>>::get()
+        }
+        fn ss58_prefix() -> u16 {
+            SS58_PREFIX
+        }
+        fn enabled_pallets() -> Vec<RuntimeString> {
+            let mut pallets = AllPalletsWithSystem::infos();
+            pallets.sort_by_key(|p| p.index);
+            pallets.into_iter().map(|p| p.name.into()).collect()
+        }
+    }
+
+    impl self::CurrencyHistory<Block> for Runtime {
+        fn balance_at(
+            account: interface::AccountId,
+            block: interface::BlockNumber,
+        ) -> Option<interface::CurrencyBalance> {
+            Currency::balance_at(&account, block)
+        }
+        fn total_issuance_at(block: interface::BlockNumber) -> Option<interface::CurrencyBalance> {
+            Currency::total_issuance_at(block)
+        }
+    }
+
+    impl self::DryRunApi<Block> for Runtime {
+        fn dry_run_call(
+            origin: OriginCaller,
+            call: RuntimeCall,
+        ) -> dry_run::CallDryRunEffects<RuntimeEvent> {
+            dry_run::dry_run_call(origin, call)
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn benchmark_metadata(extra: bool) -> (
+            Vec<frame_benchmarking::BenchmarkList>,
+            Vec<frame::deps::frame_support::traits::StorageInfo>,
+        ) {
+            use frame::deps::frame_support::traits::StorageInfoTrait;
+            use frame_benchmarking::{baseline::Pallet as BaselineBench, BenchmarkList, Benchmarking};
+            use frame_system_benchmarking::Pallet as SystemBench;
+
+            let mut list = Vec::<BenchmarkList>::new();
+            list_benchmarks!(list, extra);
+
+            (list, AllPalletsWithSystem::storage_info())
+        }
+
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig,
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, RuntimeString> {
+            use frame::deps::{
+                frame_support::traits::WhitelistedStorageKeys, sp_core::storage::TrackedStorageKey,
+            };
+            use frame_benchmarking::{baseline::Pallet as BaselineBench, BenchmarkBatch, Benchmarking};
+            use frame_system_benchmarking::Pallet as SystemBench;
+
+            let whitelist: Vec<TrackedStorageKey> =
+                AllPalletsWithSystem::whitelisted_storage_keys();
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+            add_benchmarks!(params, batches);
+
+            Ok(batches)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+            let weight = RuntimeExecutive::try_runtime_upgrade(checks).unwrap();
+            let block_weights: frame_system::limits::BlockWeights =
+                <Runtime as frame_system::Config>::BlockWeights::get();
+            (weight, block_weights.max_block)
+        }
+
+        fn execute_block(
+            block: Block,
+            state_root_check: bool,
+            signature_check: bool,
+            select: frame_try_runtime::TryStateSelect,
+        ) -> Weight {
+            RuntimeExecutive::try_execute_block(block, state_root_check, signature_check, select)
+                .expect("execute-block failed")
+        }
     }
 }
 
@@ -295,10 +801,568 @@ pub mod interface {
 
     pub use frame::runtime::types_common::OpaqueBlock;
 
-    pub type Block          = super::Block;
-    pub type AccountId      = <Runtime as frame_system::Config>::AccountId;
-    pub type Nonce          = <Runtime as frame_system::Config>::Nonce;
-    pub type Hash           = <Runtime as frame_system::Config>::Hash;
-    pub type Balance        = <Runtime as pallet_balances::Config>::Balance;
-    pub type MinimumBalance = <Runtime as pallet_balances::Config>::ExistentialDeposit;
+    pub type Block           = super::Block;
+    pub type BlockNumber     = frame_system::pallet_prelude::BlockNumberFor<Runtime>;
+    pub type AccountId       = <Runtime as frame_system::Config>::AccountId;
+    pub type Nonce           = <Runtime as frame_system::Config>::Nonce;
+    pub type Hash            = <Runtime as frame_system::Config>::Hash;
+    pub type Balance         = <Runtime as pallet_balances::Config>::Balance;
+    pub type MinimumBalance  = <Runtime as pallet_balances::Config>::ExistentialDeposit;
+    pub type CurrencyBalance = pallet_minimal_template2::Balance;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame::{
+        arithmetic::One,
+        deps::{
+            frame_support::{assert_ok, dispatch::GetDispatchInfo, weights::WeightToFee as _},
+            sp_io::TestExternalities,
+            sp_runtime::{
+                generic::{Era, SignedPayload},
+                traits::{Convert, Header as _},
+            },
+        },
+    };
+    use serde_json::Value;
+    use sp_keyring::AccountKeyring;
+
+    /// Apply the json `patch` on top of `base`, like the node does with a preset.
+    fn json_merge(base: &mut Value, patch: Value) {
+        match (base, patch) {
+            (Value::Object(base), Value::Object(patch)) => {
+                for (key, value) in patch {
+                    json_merge(base.entry(key).or_insert(Value::Null), value);
+                }
+            }
+            (base, patch) => *base = patch,
+        }
+    }
+
+    /// The state of the preset `name`.
+    fn preset_ext(name: &'static str) -> TestExternalities {
+        let preset = genesis_config_presets::get_preset(&name.into()).expect("preset exists");
+        let mut config = serde_json::to_value(RuntimeGenesisConfig::default()).unwrap();
+        json_merge(&mut config, serde_json::from_slice(&preset).unwrap());
+
+        let mut ext = TestExternalities::new_empty();
+        ext.execute_with(|| {
+            build_state::<RuntimeGenesisConfig>(serde_json::to_vec(&config).unwrap())
+                .expect("preset is valid");
+        });
+        ext
+    }
+
+    /// The state of the `development` preset.
+    pub(crate) fn development_ext() -> TestExternalities {
+        preset_ext(genesis_config_presets::DEVELOPMENT)
+    }
+
+    /// Start block 1 on top of the genesis state.
+    fn initialize_block() {
+        let header = HeaderFor::<Runtime>::new(
+            1,
+            Default::default(),
+            Default::default(),
+            System::block_hash(0),
+            Default::default(),
+        );
+        RuntimeExecutive::initialize_block(&header);
+    }
+
+    /// `call`, signed by `who` with the given `nonce` and `tip`.
+    fn signed(
+        who: AccountKeyring,
+        call: RuntimeCall,
+        nonce: interface::Nonce,
+        tip: interface::Balance,
+    ) -> ExtrinsicFor<Runtime> {
+        let extra: SignedExtra = (
+            frame_system::CheckNonZeroSender::new(),
+            frame_system::CheckSpecVersion::new(),
+            frame_system::CheckTxVersion::new(),
+            frame_system::CheckGenesis::new(),
+            frame_system::CheckEra::from(Era::Immortal),
+            frame_system::CheckNonce::from(nonce),
+            frame_system::CheckWeight::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::from(tip),
+        );
+        let payload = SignedPayload::new(call, extra).expect("signed extensions are valid");
+        let signature = payload.using_encoded(|p| who.sign(p));
+        let (call, extra, _) = payload.deconstruct();
+        ExtrinsicFor::<Runtime>::new_signed(
+            call,
+            who.to_account_id().into(),
+            signature.into(),
+            extra,
+        )
+    }
+
+    #[test]
+    fn staging_preset_has_no_keys() {
+        preset_ext(genesis_config_presets::STAGING).execute_with(|| {
+            let alice = AccountKeyring::Alice.to_account_id();
+            let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+            assert!(Sudo::sudo(RuntimeOrigin::signed(alice), Box::new(call)).is_err());
+            assert!(council::Members::<Runtime>::get().is_empty());
+            assert!(pallet_aura::Authorities::<Runtime>::get().is_empty());
+            assert!(pallet_babe::Authorities::<Runtime>::get().is_empty());
+            assert!(Grandpa::grandpa_authorities().is_empty());
+            assert_eq!(pallet_balances::TotalIssuance::<Runtime>::get(), 0);
+        });
+    }
+
+    #[test]
+    fn weight_fee_is_linear_in_execution_time() {
+        let base = WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get());
+        assert!(base.abs_diff(ExtrinsicBaseFee::get()) <= 1);
+
+        let ten_times = WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get().saturating_mul(10));
+        assert!(ten_times.abs_diff(10 * ExtrinsicBaseFee::get()) <= 10);
+        assert_eq!(WeightToFee::weight_to_fee(&Weight::zero()), 0);
+    }
+
+    #[test]
+    fn length_fee_is_per_byte() {
+        assert_eq!(TransactionPayment::length_to_fee(0), 0);
+        assert_eq!(
+            TransactionPayment::length_to_fee(100),
+            100 * TransactionByteFee::get()
+        );
+    }
+
+    #[test]
+    fn fee_multiplier_follows_block_fullness() {
+        development_ext().execute_with(|| {
+            let block_weights: frame_system::limits::BlockWeights =
+                <Runtime as frame_system::Config>::BlockWeights::get();
+            let max_normal = block_weights.get(DispatchClass::Normal).max_total.unwrap();
+
+            // a full block makes the next one more expensive.
+            System::set_block_consumed_resources(max_normal, 0);
+            assert!(FeeMultiplierUpdate::convert(Multiplier::one()) > Multiplier::one());
+
+            // a block at the target leaves fees as they are.
+            System::set_block_consumed_resources(TargetBlockFullness::get() * max_normal, 0);
+            assert_eq!(
+                FeeMultiplierUpdate::convert(Multiplier::one()),
+                Multiplier::one()
+            );
+
+            // empty blocks make it cheaper, down to the minimum.
+            System::set_block_consumed_resources(Weight::zero(), 0);
+            assert!(FeeMultiplierUpdate::convert(Multiplier::one()) < Multiplier::one());
+            assert_eq!(
+                FeeMultiplierUpdate::convert(MinimumMultiplier::get()),
+                MinimumMultiplier::get()
+            );
+        });
+    }
+
+    #[test]
+    fn transfer_pays_weight_and_length_fee() {
+        development_ext().execute_with(|| {
+            initialize_block();
+            let multiplier = Multiplier::saturating_from_integer(2);
+            pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(multiplier);
+
+            let (bob, alice) = (
+                AccountKeyring::Bob.to_account_id(),
+                AccountKeyring::Alice.to_account_id(),
+            );
+            let call = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+                dest: alice.into(),
+                value: 1_000,
+            });
+            let weight = call.get_dispatch_info().weight;
+            let xt = signed(AccountKeyring::Bob, call, 0, 0);
+            let len = xt.encoded_size() as interface::Balance;
+            let before = Balances::free_balance(&bob);
+
+            assert_ok!(RuntimeExecutive::apply_extrinsic(xt).unwrap());
+
+            let fee = WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get())
+                + len * TransactionByteFee::get()
+                + multiplier.saturating_mul_int(WeightToFee::weight_to_fee(&weight));
+            assert_eq!(Balances::free_balance(&bob), before - 1_000 - fee);
+            System::assert_has_event(
+                pallet_transaction_payment::Event::TransactionFeePaid {
+                    who: bob,
+                    actual_fee: fee,
+                    tip: 0,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn fees_and_tips_are_split() {
+        development_ext().execute_with(|| {
+            initialize_block();
+            let treasury = FeeTreasury::get().unwrap();
+            let treasury_balance = Balances::free_balance(&treasury);
+            let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+            let xt = signed(AccountKeyring::Bob, call.clone(), 0, 1_000);
+            let fee = TransactionPayment::compute_fee(
+                xt.encoded_size() as u32,
+                &call.get_dispatch_info(),
+                0,
+            );
+            let issuance = Balances::total_issuance();
+
+            assert_ok!(RuntimeExecutive::apply_extrinsic(xt).unwrap());
+
+            // without a known author, its part of the fee and the whole tip go to the treasury.
+            let burned = FeeBurn::get().mul_floor(fee);
+            assert_eq!(
+                Balances::free_balance(&treasury),
+                treasury_balance + fee - burned + 1_000
+            );
+            assert_eq!(Balances::total_issuance(), issuance - burned);
+            System::assert_has_event(
+                fees::Event::FeeSplit {
+                    burned,
+                    to_treasury: fee - burned,
+                    to_author: 0,
+                    author: None,
+                }
+                .into(),
+            );
+            System::assert_has_event(
+                fees::Event::TipSplit {
+                    burned: 0,
+                    to_treasury: 1_000,
+                    to_author: 0,
+                    author: None,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn treasury_part_does_not_depend_on_the_sudo_key() {
+        development_ext().execute_with(|| {
+            initialize_block();
+            let alice = AccountKeyring::Alice.to_account_id();
+            assert_ok!(Sudo::remove_key(RuntimeOrigin::signed(alice.clone())));
+            let treasury = FeeTreasury::get().unwrap();
+            assert_ne!(treasury, alice);
+            let (treasury_balance, alice_balance) = (
+                Balances::free_balance(&treasury),
+                Balances::free_balance(&alice),
+            );
+            let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+            let xt = signed(AccountKeyring::Bob, call.clone(), 0, 1_000);
+            let fee = TransactionPayment::compute_fee(
+                xt.encoded_size() as u32,
+                &call.get_dispatch_info(),
+                0,
+            );
+
+            assert_ok!(RuntimeExecutive::apply_extrinsic(xt).unwrap());
+
+            let burned = FeeBurn::get().mul_floor(fee);
+            assert_eq!(
+                Balances::free_balance(&treasury),
+                treasury_balance + fee - burned + 1_000
+            );
+            assert_eq!(Balances::free_balance(&alice), alice_balance);
+        });
+    }
+
+    #[test]
+    fn aura_author_is_paid_its_part_of_fees_and_tips() {
+        use frame::deps::sp_runtime::{Digest, DigestItem};
+        use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+
+        development_ext().execute_with(|| {
+            // Alice is the only authority, so she authors every slot.
+            let digest = Digest {
+                logs: vec![DigestItem::PreRuntime(
+                    AURA_ENGINE_ID,
+                    Slot::from(1).encode(),
+                )],
+            };
+            let header = Header::new(
+                1,
+                Default::default(),
+                Default::default(),
+                System::block_hash(0),
+                digest,
+            );
+            RuntimeExecutive::initialize_block(&header);
+            let alice = AccountKeyring::Alice.to_account_id();
+            assert_eq!(BlockAuthor::get(), Some(alice.clone()));
+
+            let alice_balance = Balances::free_balance(&alice);
+            let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+            let xt = signed(AccountKeyring::Bob, call.clone(), 0, 1_000);
+            let fee = TransactionPayment::compute_fee(
+                xt.encoded_size() as u32,
+                &call.get_dispatch_info(),
+                0,
+            );
+            assert_ok!(RuntimeExecutive::apply_extrinsic(xt).unwrap());
+
+            let burned = FeeBurn::get().mul_floor(fee);
+            let to_treasury = FeeToTreasury::get().mul_floor(fee);
+            let to_author = fee - burned - to_treasury;
+            let treasury = FeeTreasury::get().unwrap();
+            assert_eq!(
+                Balances::free_balance(&alice),
+                alice_balance + to_author + 1_000
+            );
+            assert_eq!(Balances::free_balance(&treasury), to_treasury);
+            System::assert_has_event(
+                fees::Event::FeeSplit {
+                    burned,
+                    to_treasury,
+                    to_author,
+                    author: Some(alice),
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn babe_author_is_found_and_randomness_is_available() {
+        use frame::{
+            deps::{
+                sp_core::crypto::VrfSecret,
+                sp_runtime::{Digest, DigestItem},
+            },
+            traits::Randomness as _,
+        };
+        use sp_consensus_babe::{
+            digests::{PreDigest, PrimaryPreDigest, SecondaryPlainPreDigest},
+            make_vrf_transcript, Slot, BABE_ENGINE_ID,
+        };
+
+        /// Author block `number` on top of `parent_hash` with `pre_digest`, returning its hash.
+        fn author_block(
+            number: interface::BlockNumber,
+            parent_hash: interface::Hash,
+            pre_digest: PreDigest,
+        ) -> interface::Hash {
+            let slot = pre_digest.slot();
+            let digest = Digest {
+                logs: vec![DigestItem::PreRuntime(BABE_ENGINE_ID, pre_digest.encode())],
+            };
+            let header = Header::new(
+                number,
+                Default::default(),
+                Default::default(),
+                parent_hash,
+                digest,
+            );
+            RuntimeExecutive::initialize_block(&header);
+            // Alice is the only authority, so she is the author of every slot.
+            assert_eq!(
+                BlockAuthor::get(),
+                Some(AccountKeyring::Alice.to_account_id())
+            );
+            assert_ok!(Timestamp::set(RuntimeOrigin::none(), *slot * SLOT_DURATION));
+            RuntimeExecutive::finalize_block().hash()
+        }
+
+        development_ext().execute_with(|| {
+            // a primary slot claim carries the output of the author's VRF.
+            let slot = Slot::from(1);
+            let transcript =
+                make_vrf_transcript(&pallet_babe::Randomness::<Runtime>::get(), slot, 0);
+            let vrf_signature = AccountKeyring::Alice
+                .pair()
+                .vrf_sign(&transcript.into_sign_data());
+            let primary = PreDigest::Primary(PrimaryPreDigest {
+                authority_index: 0,
+                slot,
+                vrf_signature,
+            });
+            let parent_hash = author_block(1, System::block_hash(0), primary);
+
+            // which is collected towards the randomness of the epochs to come.
+            let output = pallet_babe::AuthorVrfRandomness::<Runtime>::get()
+                .expect("a primary slot has a VRF output");
+            assert_ne!(output, [0; 32]);
+            assert!(pallet_babe::UnderConstruction::<Runtime>::get(0).contains(&output));
+            let (before, _) = Randomness::random(b"lottery");
+
+            // once the epoch is over, pallets draw from new, non-zero randomness.
+            let secondary = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+                authority_index: 0,
+                slot: slot + EPOCH_DURATION,
+            });
+            author_block(2, parent_hash, secondary);
+            assert_eq!(pallet_babe::EpochIndex::<Runtime>::get(), 1);
+            assert_ne!(pallet_babe::NextRandomness::<Runtime>::get(), [0; 32]);
+            let (after, _) = Randomness::random(b"lottery");
+            assert_ne!(after, before);
+        });
+    }
+
+    #[test]
+    fn chain_metadata_matches_runtime() {
+        use frame::traits::fungible;
+        use runtime_decl_for_chain_metadata::ChainMetadataV1;
+
+        assert_eq!(
+            <Runtime as ChainMetadataV1<Block>>::ss58_prefix(),
+            <<Runtime as frame_system::Config>::SS58Prefix as Get<u16>>::get()
+        );
+        assert_eq!(
+            <Runtime as ChainMetadataV1<Block>>::existential_deposit(),
+            <Balances as fungible::Inspect<_>>::minimum_balance()
+        );
+        assert_eq!(
+            <Runtime as ChainMetadataV1<Block>>::enabled_pallets(),
+            [
+                "System",
+                "Timestamp",
+                "Balances",
+                "Sudo",
+                "TransactionPayment",
+                "Template",
+                "Currency",
+                "Fees",
+                "Aura",
+                "Grandpa",
+                "Difficulty",
+                "Babe",
+                "Council"
+            ]
+            .map(RuntimeString::from)
+        );
+    }
+
+    #[test]
+    fn only_root_mints_currency() {
+        development_ext().execute_with(|| {
+            let alice = AccountKeyring::Alice.to_account_id();
+            let issuance = pallet_minimal_template2::TotalIssuance::<Runtime>::get();
+
+            assert_eq!(
+                Currency::mint_unsafe(RuntimeOrigin::signed(alice.clone()), alice.clone(), 100),
+                Err(DispatchError::BadOrigin)
+            );
+            assert_eq!(
+                pallet_minimal_template2::TotalIssuance::<Runtime>::get(),
+                issuance
+            );
+
+            assert_ok!(Currency::mint_unsafe(RuntimeOrigin::root(), alice, 100));
+            assert_eq!(
+                pallet_minimal_template2::TotalIssuance::<Runtime>::get(),
+                Some(issuance.unwrap_or(0) + 100)
+            );
+        });
+    }
+
+    #[test]
+    fn dry_run_reports_effects_without_applying_them() {
+        use runtime_decl_for_dry_run_api::DryRunApiV1;
+
+        development_ext().execute_with(|| {
+            let alice = AccountKeyring::Alice.to_account_id();
+            let bob = AccountKeyring::Bob.to_account_id();
+            let bob_balance = Balances::free_balance(&bob);
+            let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+                dest: bob.clone().into(),
+                value: 1_000,
+            });
+            let info = transfer.get_dispatch_info();
+
+            let effects = <Runtime as DryRunApiV1<Block>>::dry_run_call(
+                frame_system::RawOrigin::Signed(alice.clone()).into(),
+                transfer.clone(),
+            );
+            assert!(effects.execution_result.is_ok());
+            assert!(effects.emitted_events.contains(&RuntimeEvent::Balances(
+                pallet_balances::Event::Transfer {
+                    from: alice,
+                    to: bob.clone(),
+                    amount: 1_000,
+                }
+            )));
+            assert!(effects.weight.all_lte(info.weight));
+            assert_eq!(Balances::free_balance(&bob), bob_balance);
+            assert_eq!(System::events(), vec![]);
+
+            // a failing call reports why it failed.
+            let effects = <Runtime as DryRunApiV1<Block>>::dry_run_call(
+                frame_system::RawOrigin::Signed(bob.clone()).into(),
+                RuntimeCall::Balances(pallet_balances::Call::force_set_balance {
+                    who: bob.into(),
+                    new_free: 0,
+                }),
+            );
+            assert_eq!(
+                effects.execution_result.map_err(|e| e.error),
+                Err(DispatchError::BadOrigin)
+            );
+            assert_eq!(effects.emitted_events, vec![]);
+        });
+    }
+
+    #[test]
+    fn difficulty_steers_towards_target_block_time() {
+        development_ext().execute_with(|| {
+            let minimum = MinimumDifficulty::get();
+            assert_eq!(Difficulty::difficulty(), minimum);
+
+            // the first block has no block time to adjust by.
+            pallet_timestamp::Now::<Runtime>::put(1_000);
+            <Difficulty as Hooks<_>>::on_finalize(1);
+            assert_eq!(Difficulty::difficulty(), minimum);
+
+            // blocks twice as fast as the target double the difficulty.
+            pallet_timestamp::Now::<Runtime>::put(1_000 + POW_TARGET_BLOCK_TIME / 2);
+            <Difficulty as Hooks<_>>::on_finalize(2);
+            assert_eq!(Difficulty::difficulty(), minimum * 2);
+
+            // much faster blocks adjust it by at most a factor of 2.
+            pallet_timestamp::Now::<Runtime>::put(1_001 + POW_TARGET_BLOCK_TIME / 2);
+            <Difficulty as Hooks<_>>::on_finalize(3);
+            assert_eq!(Difficulty::difficulty(), minimum * 4);
+
+            // and slow blocks lower it, but never below the minimum.
+            pallet_timestamp::Now::<Runtime>::put(1_001 + 3 * POW_TARGET_BLOCK_TIME);
+            <Difficulty as Hooks<_>>::on_finalize(4);
+            assert_eq!(Difficulty::difficulty(), minimum * 2);
+            assert_eq!(
+                Difficulty::adjust(minimum, 10 * POW_TARGET_BLOCK_TIME),
+                minimum
+            );
+        });
+    }
+
+    #[test]
+    fn difficulty_adjustment_is_clamped_at_extreme_block_times() {
+        let minimum = MinimumDifficulty::get();
+        let difficulty = minimum * 8;
+
+        // instant blocks at most double the difficulty, saturating at the largest one.
+        assert_eq!(Difficulty::adjust(difficulty, 0), difficulty * 2);
+        assert_eq!(Difficulty::adjust(U256::MAX, 0), U256::MAX);
+
+        // and the slowest blocks at most halve it, but never below the minimum.
+        assert_eq!(Difficulty::adjust(difficulty, u64::MAX), difficulty / 2);
+        assert_eq!(Difficulty::adjust(minimum, u64::MAX), minimum);
+        assert_eq!(Difficulty::adjust(U256::MAX, u64::MAX), U256::MAX / 2);
+    }
+
+    #[cfg(feature = "try-runtime")]
+    #[test]
+    fn development_state_passes_try_runtime_checks() {
+        use frame_try_runtime::{runtime_decl_for_try_runtime::TryRuntimeV1, UpgradeCheckSelect};
+
+        development_ext().execute_with(|| {
+            // runs the `try_state` checks of every pallet, including the custom ones, and panics
+            // if any fails.
+            <Runtime as TryRuntimeV1<Block>>::on_runtime_upgrade(UpgradeCheckSelect::All);
+        });
+    }
 }